@@ -13,16 +13,62 @@ pub const DEFAULT_PLATFORM_FEE: u16 = 500;     // 5% in basis points
 // NFT config
 pub const MAX_METADATA_ITEMS: usize = 10;
 pub const MAX_STREAMING_BATCH_SIZE: usize = 50;
+pub const MAX_UPSTREAM_BASIS_POINTS: u16 = 5_000; // Derivatives keep at least half their revenue
+pub const NFT_SYMBOL: &str = "SNDM";
 
 // Collection configuration
 pub const SOUND_MINT_COLLECTION_PREFIX: &[u8] = b"sound_mint_collection";
 
+// Revenue epochs
+pub const LEGACY_REVENUE_PERIOD: u32 = 0; // Holds revenue collected before epochs existed
+
 // Treasury configuration
 pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const CONFIG_TIMELOCK_SECONDS: i64 = 2 * 24 * 60 * 60; // 48 hours
+pub const ADMIN_PROPOSAL_LIFETIME_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+
+// Emergency pause flags (bitmask on Treasury.paused_flags)
+pub const PAUSE_MINTING: u8 = 1 << 0;
+pub const PAUSE_ROYALTY_ISSUANCE: u8 = 1 << 1;
+pub const PAUSE_REVENUE_TRACKING: u8 = 1 << 2;
+pub const PAUSE_STREAMING: u8 = 1 << 3;
+pub const PAUSE_CLAIMS: u8 = 1 << 4;
+pub const PAUSE_COLLECTIONS: u8 = 1 << 5;
+pub const PAUSE_ALL: u8 = PAUSE_MINTING
+    | PAUSE_ROYALTY_ISSUANCE
+    | PAUSE_REVENUE_TRACKING
+    | PAUSE_STREAMING
+    | PAUSE_CLAIMS
+    | PAUSE_COLLECTIONS;
+
+// Artist delegate permissions (bitmask)
+pub const DELEGATE_MINT_TRACKS: u8 = 1 << 0;
+pub const DELEGATE_UPDATE_METADATA: u8 = 1 << 1;
+pub const DELEGATE_CREATE_SPLITS: u8 = 1 << 2;
+pub const DELEGATE_MANAGE_COLLECTIONS: u8 = 1 << 3;
+pub const DELEGATE_ALL: u8 = DELEGATE_MINT_TRACKS
+    | DELEGATE_UPDATE_METADATA
+    | DELEGATE_CREATE_SPLITS
+    | DELEGATE_MANAGE_COLLECTIONS;
 
 // PDA seeds
 pub const ARTIST_PROFILE_SEED: &[u8] = b"artist_profile";
 pub const MASTER_NFT_SEED: &[u8] = b"master_nft";
 pub const ROYALTY_SPLIT_SEED: &[u8] = b"royalty_split";
 pub const ROYALTY_NFT_SEED: &[u8] = b"royalty_nft";
-pub const REVENUE_TRACKER_SEED: &[u8] = b"revenue_tracker";
\ No newline at end of file
+pub const REVENUE_TRACKER_SEED: &[u8] = b"revenue_tracker";
+pub const REVENUE_EPOCH_SEED: &[u8] = b"revenue_epoch";
+pub const ROYALTY_STATEMENT_SEED: &[u8] = b"royalty_statement";
+pub const ADMIN_PROPOSAL_SEED: &[u8] = b"admin_proposal";
+pub const ADMIN_ROLE_SEED: &[u8] = b"admin_role";
+pub const FEE_OVERRIDE_SEED: &[u8] = b"fee_override";
+pub const FEE_VOUCHER_SEED: &[u8] = b"fee_voucher";
+pub const ARTIST_HANDLE_SEED: &[u8] = b"artist_handle";
+pub const ARTIST_DELEGATE_SEED: &[u8] = b"artist_delegate";
+pub const LABEL_SEED: &[u8] = b"label";
+pub const LABEL_MEMBERSHIP_SEED: &[u8] = b"label_membership";
+pub const VERIFICATION_REQUEST_SEED: &[u8] = b"verification_request";
+pub const NFT_AUTHORITY_SEED: &[u8] = b"nft_authority";
+pub const IDENTIFIER_SEED: &[u8] = b"identifier";
+pub const AUDIO_FINGERPRINT_SEED: &[u8] = b"audio_fingerprint";
+pub const DERIVATIVE_LINK_SEED: &[u8] = b"derivative_link";
\ No newline at end of file
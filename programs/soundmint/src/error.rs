@@ -58,4 +58,190 @@ pub enum CustomError {
     
     #[msg("Collection not found")]
     CollectionNotFound,
-}
\ No newline at end of file
+
+    #[msg("Invalid revenue epoch period")]
+    InvalidEpochPeriod,
+
+    #[msg("Revenue epoch is closed")]
+    EpochClosed,
+
+    #[msg("Revenue epoch has not been closed yet")]
+    EpochNotClosed,
+
+    #[msg("Revenue epoch account not provided")]
+    EpochNotFound,
+
+    #[msg("Claims would exceed the revenue recorded for this epoch")]
+    EpochOverClaimed,
+
+    #[msg("Revenue epoch still has revenue to pay out")]
+    EpochNotSettled,
+
+    #[msg("Every royalty statement of the epoch has to be closed with it")]
+    RoyaltyStatementsMissing,
+
+    #[msg("Royalty split has to be migrated to revenue epochs first")]
+    RoyaltySplitNotMigrated,
+
+    #[msg("Royalty split revenue has already been migrated")]
+    RevenueAlreadyMigrated,
+
+    #[msg("Account already has the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("This action must be approved through an admin multisig proposal")]
+    MultisigRequired,
+
+    #[msg("Admin multisig is not enabled")]
+    MultisigNotEnabled,
+
+    #[msg("Invalid admin set or approval threshold")]
+    InvalidAdminSet,
+
+    #[msg("Signer is not part of the admin set")]
+    NotAnAdmin,
+
+    #[msg("Admin has already approved this proposal")]
+    AlreadyApproved,
+
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Proposal does not have enough approvals")]
+    InsufficientApprovals,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Proposal is still open")]
+    ProposalStillOpen,
+
+    #[msg("Required account for this admin action was not provided")]
+    MissingActionAccount,
+
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("No treasury config change is pending")]
+    NoPendingConfig,
+
+    #[msg("This instruction is currently paused")]
+    Paused,
+
+    #[msg("Fee schedule account not supplied")]
+    FeeAccountNotFound,
+
+    #[msg("Fee voucher is expired or has no uses left")]
+    VoucherNotRedeemable,
+
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+
+    #[msg("Artist name must contain at least one letter or digit")]
+    InvalidHandle,
+
+    #[msg("Artist handle is already taken")]
+    HandleTaken,
+
+    #[msg("Artist handle is reserved")]
+    HandleReserved,
+
+    #[msg("Artist handle accounts must be supplied when renaming")]
+    HandleAccountMissing,
+
+    #[msg("Artist is not verified")]
+    ArtistNotVerified,
+
+    #[msg("Artist profile still has live tracks")]
+    ArtistHasTracks,
+
+    #[msg("Track still has unclaimed revenue")]
+    UnclaimedRevenue,
+
+    #[msg("Track still has royalty NFTs outstanding")]
+    RoyaltyNftsOutstanding,
+
+    #[msg("The artist's label account must be supplied")]
+    LabelRequired,
+
+    #[msg("Artist already belongs to a label")]
+    AlreadyInLabel,
+
+    #[msg("Label invite has already been accepted")]
+    AlreadyLabelMember,
+
+    #[msg("Too many label managers")]
+    TooManyManagers,
+
+    #[msg("A verification request is already awaiting review")]
+    VerificationPending,
+
+    #[msg("Verification request is not awaiting review")]
+    VerificationNotPending,
+
+    #[msg("Invalid verification tier")]
+    InvalidVerificationTier,
+
+    #[msg("Royalty split has more payees than the metadata creators list allows")]
+    TooManyCreators,
+
+    #[msg("A new metadata URI is required to keep token metadata in sync")]
+    MetadataUriRequired,
+
+    #[msg("The holder's token account is required to change transferability")]
+    HolderTokenAccountRequired,
+
+    #[msg("Invalid master NFT status transition")]
+    InvalidStatusTransition,
+
+    #[msg("Track is under moderation")]
+    TrackUnderModeration,
+
+    #[msg("Track is not accepting revenue in its current status")]
+    TrackNotEarning,
+
+    #[msg("Claims are suspended while the track is under moderation")]
+    ClaimsSuspended,
+
+    #[msg("Content hash is missing")]
+    InvalidContentHash,
+
+    #[msg("Invalid audio duration or sample rate")]
+    InvalidAudioSpec,
+
+    #[msg("Invalid ISRC or UPC")]
+    InvalidIdentifier,
+
+    #[msg("Identifier registration account does not match the supplied code")]
+    IdentifierAccountMismatch,
+
+    #[msg("Target already has an identifier of this kind")]
+    IdentifierAlreadySet,
+
+    #[msg("Recording is already registered by another track")]
+    FingerprintCollision,
+
+    #[msg("A declared version must share its fingerprint with an existing track")]
+    InvalidVersionLink,
+
+    #[msg("Fingerprint registry entry does not match the track")]
+    FingerprintAccountMismatch,
+
+    #[msg("Invalid derivative link")]
+    InvalidDerivativeLink,
+
+    #[msg("Derivative link is not pending")]
+    DerivativeLinkNotPending,
+
+    #[msg("Upstream shares exceed the allowed maximum")]
+    UpstreamShareTooHigh,
+
+    #[msg("Parent royalty split or revenue epoch not provided")]
+    UpstreamAccountNotFound,
+
+    #[msg("Fee voucher exceeds the uses or validity allowed per issuance")]
+    FeeVoucherLimitExceeded,
+}
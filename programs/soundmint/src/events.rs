@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::state::{AudioCodec, DerivativeKind, IdentifierKind, MasterNftStatus, VerificationTier};
+
+#[event]
+pub struct FeeVoucherIssued {
+    pub voucher: Pubkey,
+    pub beneficiary: Pubkey,
+    pub issuer: Pubkey,
+    pub uses: u16,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct FeeVoucherRedeemed {
+    pub voucher: Pubkey,
+    pub beneficiary: Pubkey,
+    pub master_nft: Pubkey,
+    pub waived_fee: u64,
+    pub remaining_uses: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeVoucherRevoked {
+    pub voucher: Pubkey,
+    pub beneficiary: Pubkey,
+    pub unused_uses: u16,
+}
+
+#[event]
+pub struct VerificationRequested {
+    pub request: Pubkey,
+    pub artist_profile: Pubkey,
+    pub requested_tier: VerificationTier,
+    pub evidence_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationReviewed {
+    pub request: Pubkey,
+    pub artist_profile: Pubkey,
+    pub reviewer: Pubkey,
+    pub approved: bool,
+    pub tier: VerificationTier,
+    pub reason_code: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MasterNftModerated {
+    pub master_nft: Pubkey,
+    pub moderator: Pubkey,
+    pub status: MasterNftStatus,
+    pub reason_code: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MasterNftMinted {
+    pub master_nft: Pubkey,
+    pub artist_profile: Pubkey,
+    pub mint: Pubkey,
+    pub audio_hash: [u8; 32],
+    pub artwork_hash: [u8; 32],
+    pub duration_seconds: u32,
+    pub codec: AudioCodec,
+    pub sample_rate_hz: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IdentifierReassigned {
+    pub registration: Pubkey,
+    pub kind: IdentifierKind,
+    pub code: String,
+    pub previous_holder: Pubkey,
+    pub new_holder: Pubkey,
+    pub moderator: Pubkey,
+    pub reason_code: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FingerprintReassigned {
+    pub audio_fingerprint: Pubkey,
+    pub previous_holder: Pubkey,
+    pub new_holder: Pubkey, // Default when the claim was revoked
+    pub moderator: Pubkey,
+    pub reason_code: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DerivativeDeclared {
+    pub link: Pubkey,
+    pub derivative: Pubkey,
+    pub parent: Pubkey,
+    pub kind: DerivativeKind,
+    pub upstream_basis_points: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DerivativeAccepted {
+    pub link: Pubkey,
+    pub derivative: Pubkey,
+    pub parent: Pubkey,
+    pub upstream_basis_points: u16,
+    pub timestamp: i64,
+}
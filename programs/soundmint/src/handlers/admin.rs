@@ -1,5 +1,21 @@
-pub mod treasury;
+pub mod treasury_management;
 pub mod artist;
+pub mod epoch;
+pub mod multisig;
+pub mod pause;
+pub mod roles;
+pub mod fees;
+pub mod moderation;
+pub mod identifiers;
+pub mod migration;
 
-pub use treasury::*;
-pub use artist::*;
\ No newline at end of file
+pub use treasury_management::*;
+pub use artist::*;
+pub use epoch::*;
+pub use multisig::*;
+pub use pause::*;
+pub use roles::*;
+pub use fees::*;
+pub use moderation::*;
+pub use identifiers::*;
+pub use migration::*;
\ No newline at end of file
@@ -2,20 +2,45 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::CustomError;
 use crate::constants::*;
+use crate::events::VerificationReviewed;
 
-pub fn verify_artist(
+pub(crate) fn verify_artist(
     context: Context<VerifyArtistAccountConstraints>,
     verify: bool
 ) -> Result<()> {
     let artist_profile = &mut context.accounts.artist_profile;
     
-    // Only treasury authority can verify artists
+    // Only treasury authority or a verifier can verify artists
     require!(
-        context.accounts.treasury.authority == context.accounts.authority.key(),
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::Verifier
+        ),
         CustomError::Unauthorized
     );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
     
+    apply_artist_verification(artist_profile, verify)
+}
+
+// Shared by the direct admin instruction and multisig proposal execution
+pub(crate) fn apply_artist_verification(
+    artist_profile: &mut ArtistProfile,
+    verify: bool
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // A direct verification grants the base tier unless a higher one was already reviewed in
     artist_profile.is_verified = verify;
+    if !verify {
+        artist_profile.verification_tier = VerificationTier::Unverified;
+        artist_profile.verified_at = 0;
+    } else if artist_profile.verification_tier == VerificationTier::Unverified {
+        artist_profile.verification_tier = VerificationTier::Standard;
+        artist_profile.verified_at = clock.unix_timestamp;
+    }
     
     if verify {
         msg!("Artist {} has been verified", artist_profile.name);
@@ -26,6 +51,145 @@ pub fn verify_artist(
     Ok(())
 }
 
+pub(crate) fn review_verification_request(
+    context: Context<ReviewVerificationRequestAccountConstraints>,
+    approve: bool,
+    tier: Option<VerificationTier>,
+    reason_code: u8
+) -> Result<()> {
+    let verification_request = &mut context.accounts.verification_request;
+    let artist_profile = &mut context.accounts.artist_profile;
+    let reviewer = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &reviewer,
+            context.accounts.role.as_deref(),
+            Role::Verifier
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    require!(
+        verification_request.status == VerificationStatus::Pending,
+        CustomError::VerificationNotPending
+    );
+    
+    // Reviewers may grant a different tier than the one requested
+    let tier = tier.unwrap_or(verification_request.requested_tier);
+    require!(tier != VerificationTier::Unverified, CustomError::InvalidVerificationTier);
+    
+    if approve {
+        verification_request.status = VerificationStatus::Approved;
+        artist_profile.is_verified = true;
+        artist_profile.verification_tier = tier;
+        artist_profile.verified_at = clock.unix_timestamp;
+        artist_profile.updated_at = clock.unix_timestamp;
+    } else {
+        // A rejected upgrade leaves any existing verification in place
+        verification_request.status = VerificationStatus::Rejected;
+    }
+    
+    verification_request.record_decision(VerificationDecision {
+        approved: approve,
+        tier,
+        reason_code,
+        reviewer,
+        decided_at: clock.unix_timestamp,
+    });
+    
+    emit!(VerificationReviewed {
+        request: verification_request.key(),
+        artist_profile: artist_profile.key(),
+        reviewer,
+        approved: approve,
+        tier,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    if approve {
+        msg!("Verification approved for artist: {}", artist_profile.name);
+    } else {
+        msg!("Verification rejected for artist: {} (reason {})", artist_profile.name, reason_code);
+    }
+    Ok(())
+}
+
+pub(crate) fn reserve_artist_handle(
+    context: Context<ReserveArtistHandleAccountConstraints>,
+    name: String
+) -> Result<()> {
+    let artist_handle = &mut context.accounts.artist_handle;
+    let artist_profile = &context.accounts.artist_profile;
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::Verifier
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    // Protected names are only held for artists the platform has verified
+    require!(artist_profile.is_verified, CustomError::ArtistNotVerified);
+    
+    let handle = ArtistHandle::normalize(&name);
+    require!(!handle.is_empty(), CustomError::InvalidHandle);
+    require!(
+        !artist_handle.is_claimed() || artist_handle.artist_profile == artist_profile.key(),
+        CustomError::HandleTaken
+    );
+    
+    if artist_handle.handle.is_empty() {
+        artist_handle.handle = handle;
+        artist_handle.artist_profile = Pubkey::default();
+        artist_handle.created_at = clock.unix_timestamp;
+        artist_handle.bump = context.bumps.artist_handle;
+    }
+    artist_handle.reserved_for = Some(artist_profile.key());
+    artist_handle.updated_at = clock.unix_timestamp;
+    
+    msg!("Artist handle {} reserved for {}", artist_handle.handle, artist_profile.name);
+    Ok(())
+}
+
+pub(crate) fn release_artist_handle_reservation(
+    context: Context<ReleaseArtistHandleReservationAccountConstraints>
+) -> Result<()> {
+    let artist_handle = &mut context.accounts.artist_handle;
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::Verifier
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    require!(artist_handle.reserved_for.is_some(), CustomError::InvalidData);
+    
+    artist_handle.reserved_for = None;
+    artist_handle.updated_at = clock.unix_timestamp;
+    
+    msg!("Artist handle reservation released: {}", artist_handle.handle);
+    
+    // Nobody holds the handle, so free it up entirely
+    if !artist_handle.is_claimed() {
+        artist_handle.close(context.accounts.authority.to_account_info())?;
+    }
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct VerifyArtistAccountConstraints<'info> {
     #[account(mut)]
@@ -42,10 +206,117 @@ pub struct VerifyArtistAccountConstraints<'info> {
     
     #[account(
         seeds = [TREASURY_SEED],
-        bump = treasury.bump,
-        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReviewVerificationRequestAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        mut,
+        seeds = [VERIFICATION_REQUEST_SEED, artist_profile.key().as_ref()],
+        bump = verification_request.bump
+    )]
+    pub verification_request: Account<'info, VerificationRequest>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
     )]
     pub treasury: Account<'info, Treasury>,
     
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ReserveArtistHandleAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    // Verified artist the handle is protected for
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ArtistHandle::INIT_SPACE,
+        seeds = [ARTIST_HANDLE_SEED, &ArtistHandle::seed_hash(&name)],
+        bump
+    )]
+    pub artist_handle: Account<'info, ArtistHandle>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseArtistHandleReservationAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    #[account(
+        mut,
+        seeds = [ARTIST_HANDLE_SEED, &ArtistHandle::seed_hash(&artist_handle.handle)],
+        bump = artist_handle.bump
+    )]
+    pub artist_handle: Account<'info, ArtistHandle>,
+    
+    pub system_program: Program<'info, System>,
+}
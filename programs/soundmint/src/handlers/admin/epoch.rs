@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::handlers::revenue::initialize_revenue_epoch;
+use crate::handlers::program_accounts::resize_program_account;
+
+pub(crate) fn close_epoch(
+    context: Context<CloseEpochAccountConstraints>
+) -> Result<()> {
+    let revenue_epoch = &mut context.accounts.revenue_epoch;
+    let clock = Clock::get()?;
+
+    require!(!revenue_epoch.is_closed, CustomError::EpochClosed);
+
+    // A period can only be frozen once it has ended
+    require!(
+        revenue_epoch.period < RevenueEpoch::period_for_timestamp(clock.unix_timestamp),
+        CustomError::InvalidEpochPeriod
+    );
+
+    revenue_epoch.is_closed = true;
+    revenue_epoch.closed_at = clock.unix_timestamp;
+
+    msg!(
+        "Revenue epoch {} closed with {} lamports for track: {}",
+        revenue_epoch.period,
+        revenue_epoch.total_revenue,
+        context.accounts.master_nft.title
+    );
+    Ok(())
+}
+
+// Splits created before revenue epochs are grown to the current layout, and the revenue
+// they collected until then becomes a closed legacy epoch that holders claim like any other.
+// The track's existing royalty NFTs are passed as remaining accounts so they are counted.
+// The treasury and master NFT have to be migrated first.
+pub(crate) fn migrate_royalty_split(
+    context: Context<MigrateRoyaltySplitAccountConstraints>
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let royalty_split_info = context.accounts.royalty_split.to_account_info();
+    require!(royalty_split_info.owner == &crate::ID, CustomError::InvalidRoyaltySplit);
+
+    if royalty_split_info.data_len() < RoyaltySplit::INIT_SPACE {
+        resize_program_account(
+            &royalty_split_info,
+            &context.accounts.authority.to_account_info(),
+            &context.accounts.system_program.to_account_info(),
+            RoyaltySplit::INIT_SPACE,
+        )?;
+    }
+
+    let mut royalty_split = RoyaltySplit::try_deserialize(&mut &royalty_split_info.try_borrow_data()?[..])?;
+    require!(
+        royalty_split.master_nft == context.accounts.master_nft.key(),
+        CustomError::InvalidRoyaltySplit
+    );
+    require!(!royalty_split.revenue_migrated, CustomError::RevenueAlreadyMigrated);
+
+    let revenue_epoch = &mut context.accounts.revenue_epoch;
+    initialize_revenue_epoch(
+        revenue_epoch,
+        context.accounts.master_nft.key(),
+        LEGACY_REVENUE_PERIOD,
+        context.bumps.revenue_epoch,
+        context.accounts.authority.key(),
+        clock.unix_timestamp,
+    );
+    revenue_epoch.record_revenue(royalty_split.total_revenue_collected, "legacy");
+    revenue_epoch.is_closed = true;
+    revenue_epoch.closed_at = clock.unix_timestamp;
+
+    royalty_split.revenue_epoch_count = 1;
+    
+    let mut registered_royalty_nfts: Vec<Pubkey> = Vec::new();
+    for royalty_nft_info in context.remaining_accounts {
+        require!(royalty_nft_info.owner == &crate::ID, CustomError::InvalidData);
+        require!(!registered_royalty_nfts.contains(royalty_nft_info.key), CustomError::InvalidData);
+        
+        let royalty_nft = RoyaltyNft::try_deserialize(&mut &royalty_nft_info.try_borrow_data()?[..])?;
+        require!(
+            royalty_nft.master_nft == context.accounts.master_nft.key(),
+            CustomError::InvalidRoyaltySplit
+        );
+        
+        royalty_split.royalty_nft_count = royalty_split.royalty_nft_count.checked_add(1).unwrap();
+        royalty_split.minted_basis_points = royalty_split.minted_basis_points
+            .checked_add(royalty_nft.share_basis_points).unwrap();
+        registered_royalty_nfts.push(royalty_nft_info.key());
+    }
+    
+    royalty_split.revenue_migrated = true;
+    royalty_split.try_serialize(&mut *royalty_split_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Migrated {} lamports of pre-epoch revenue for track: {}",
+        revenue_epoch.total_revenue,
+        context.accounts.master_nft.title
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseEpochAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump
+    )]
+    pub master_nft: Account<'info, MasterNft>,
+
+    #[account(
+        mut,
+        seeds = [REVENUE_EPOCH_SEED, master_nft.key().as_ref(), &revenue_epoch.period.to_le_bytes()],
+        bump = revenue_epoch.bump,
+        constraint = revenue_epoch.master_nft == master_nft.key() @ CustomError::InvalidData
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRoyaltySplitAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump
+    )]
+    pub master_nft: Account<'info, MasterNft>,
+
+    /// CHECK: Royalty split that may still have its pre-epoch layout, grown and checked in the handler
+    #[account(
+        mut,
+        seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
+        bump
+    )]
+    pub royalty_split: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RevenueEpoch::INIT_SPACE,
+        seeds = [REVENUE_EPOCH_SEED, master_nft.key().as_ref(), &LEGACY_REVENUE_PERIOD.to_le_bytes()],
+        bump
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+
+    pub system_program: Program<'info, System>,
+}
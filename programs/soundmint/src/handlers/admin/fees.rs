@@ -0,0 +1,511 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::events::{FeeVoucherIssued, FeeVoucherRevoked};
+use crate::handlers::program_accounts::{close_program_account, create_program_account};
+use super::treasury_management::{schedule_referral_fee, UpdateTreasuryConfigAccountConstraints};
+
+pub(crate) fn set_fee_override(
+    context: Context<SetFeeOverrideAccountConstraints>,
+    mint_fee: Option<u64>,
+    platform_fee_basis_points: Option<u16>,
+    note: String
+) -> Result<()> {
+    let fee_override = &mut context.accounts.fee_override;
+    let clock = Clock::get()?;
+
+    // Only the authority or a fee manager can change fees
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    // A new override starts out neutral until the scheduled values take effect
+    if fee_override.artist_profile == Pubkey::default() {
+        fee_override.artist_profile = context.accounts.artist_profile.key();
+        fee_override.bump = context.bumps.fee_override;
+    }
+
+    schedule_fee_override(
+        fee_override,
+        mint_fee,
+        platform_fee_basis_points,
+        note,
+        context.accounts.authority.key(),
+        clock.unix_timestamp,
+    )
+}
+
+pub(crate) fn remove_fee_override(
+    context: Context<RemoveFeeOverrideAccountConstraints>
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    let fee_override = &mut context.accounts.fee_override;
+    if clear_fee_override(fee_override, clock.unix_timestamp) {
+        fee_override.close(context.accounts.authority.to_account_info())?;
+    }
+    Ok(())
+}
+
+pub(crate) fn issue_fee_voucher(
+    context: Context<IssueFeeVoucherAccountConstraints>,
+    uses: u16,
+    expires_at: i64
+) -> Result<()> {
+    let fee_voucher = &mut context.accounts.fee_voucher;
+    let clock = Clock::get()?;
+
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    if fee_voucher.beneficiary == Pubkey::default() {
+        fee_voucher.beneficiary = context.accounts.artist_profile.key();
+        fee_voucher.redeemed_count = 0;
+        fee_voucher.bump = context.bumps.fee_voucher;
+    }
+
+    schedule_fee_voucher(
+        fee_voucher,
+        context.accounts.authority.key(),
+        uses,
+        expires_at,
+        clock.unix_timestamp,
+    )?;
+    emit_fee_voucher_issued(fee_voucher.key(), fee_voucher);
+
+    msg!("Fee voucher with {} uses issued to artist: {}", uses, context.accounts.artist_profile.name);
+    Ok(())
+}
+
+pub(crate) fn revoke_fee_voucher(
+    context: Context<RevokeFeeVoucherAccountConstraints>
+) -> Result<()> {
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+
+    let fee_voucher = &context.accounts.fee_voucher;
+    emit!(FeeVoucherRevoked {
+        voucher: fee_voucher.key(),
+        beneficiary: fee_voucher.beneficiary,
+        unused_uses: fee_voucher.remaining_uses,
+    });
+
+    msg!("Fee voucher revoked for artist profile: {}", fee_voucher.beneficiary);
+    Ok(())
+}
+
+pub(crate) fn set_referral_fee(
+    context: Context<UpdateTreasuryConfigAccountConstraints>,
+    referral_fee_basis_points: u16
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+
+    require!(
+        AdminRole::has_access(
+            treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    // Goes through the same timelock as the other fee settings
+    schedule_referral_fee(treasury, referral_fee_basis_points, clock.unix_timestamp)
+}
+
+pub(crate) fn validate_fee_override(
+    mint_fee: Option<u64>,
+    platform_fee_basis_points: Option<u16>,
+    note: &str,
+) -> Result<()> {
+    require!(
+        mint_fee.is_some() || platform_fee_basis_points.is_some(),
+        CustomError::InvalidData
+    );
+    require!(note.len() <= FeeOverride::MAX_NOTE_LENGTH, CustomError::StringTooLong);
+
+    if let Some(fee_basis_points) = platform_fee_basis_points {
+        require!(
+            fee_basis_points <= TOTAL_BASIS_POINTS,
+            CustomError::InvalidFeePercentage
+        );
+    }
+    Ok(())
+}
+
+// Override changes wait out the same timelock as treasury fee changes
+fn schedule_fee_override(
+    fee_override: &mut FeeOverride,
+    mint_fee: Option<u64>,
+    platform_fee_basis_points: Option<u16>,
+    note: String,
+    set_by: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    validate_fee_override(mint_fee, platform_fee_basis_points, &note)?;
+
+    // Land a matured change first so rescheduling never discards it
+    fee_override.apply_pending_change(timestamp);
+
+    let effective_at = timestamp.checked_add(CONFIG_TIMELOCK_SECONDS).unwrap();
+    fee_override.pending_change = Some(PendingFeeOverride {
+        mint_fee,
+        platform_fee_basis_points,
+        effective_at,
+    });
+    fee_override.note = note;
+    fee_override.set_by = set_by;
+    fee_override.updated_at = timestamp;
+
+    msg!("Fee override for artist profile {} scheduled for {}", fee_override.artist_profile, effective_at);
+    Ok(())
+}
+
+// Returns true when the override no longer affects any fee and can be closed right away;
+// otherwise the return to the default schedule is timelocked
+fn clear_fee_override(fee_override: &mut FeeOverride, timestamp: i64) -> bool {
+    fee_override.apply_pending_change(timestamp);
+
+    if fee_override.is_neutral() {
+        msg!("Fee override removed for artist profile: {}", fee_override.artist_profile);
+        return true;
+    }
+
+    let effective_at = timestamp.checked_add(CONFIG_TIMELOCK_SECONDS).unwrap();
+    fee_override.pending_change = Some(PendingFeeOverride {
+        mint_fee: None,
+        platform_fee_basis_points: None,
+        effective_at,
+    });
+    fee_override.updated_at = timestamp;
+
+    msg!("Fee override for artist profile {} lapses at {}", fee_override.artist_profile, effective_at);
+    false
+}
+
+// Multisig proposals cannot declare the override as `init_if_needed`, so it is created here
+pub(crate) fn apply_fee_override<'info>(
+    fee_override_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    artist_profile: Pubkey,
+    mint_fee: Option<u64>,
+    platform_fee_basis_points: Option<u16>,
+    note: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let (address, bump) = Pubkey::find_program_address(
+        &[FEE_OVERRIDE_SEED, artist_profile.as_ref()],
+        &crate::ID
+    );
+    require!(fee_override_info.key() == address, CustomError::MissingActionAccount);
+
+    let mut fee_override = match FeeOverride::load(fee_override_info)? {
+        Some(fee_override) => fee_override,
+        None => {
+            create_program_account(
+                fee_override_info,
+                payer,
+                system_program,
+                &[FEE_OVERRIDE_SEED, artist_profile.as_ref(), &[bump]],
+                FeeOverride::INIT_SPACE,
+            )?;
+
+            FeeOverride {
+                artist_profile,
+                mint_fee: None,
+                platform_fee_basis_points: None,
+                note: String::new(),
+                set_by: payer.key(),
+                updated_at: clock.unix_timestamp,
+                bump,
+                pending_change: None,
+            }
+        }
+    };
+
+    schedule_fee_override(
+        &mut fee_override,
+        mint_fee,
+        platform_fee_basis_points,
+        note,
+        payer.key(),
+        clock.unix_timestamp,
+    )?;
+    fee_override.try_serialize(&mut &mut fee_override_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+pub(crate) fn apply_remove_fee_override<'info>(
+    fee_override_info: &AccountInfo<'info>,
+    rent_destination: &AccountInfo<'info>,
+    artist_profile: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    let address = Pubkey::find_program_address(
+        &[FEE_OVERRIDE_SEED, artist_profile.as_ref()],
+        &crate::ID
+    ).0;
+    require!(fee_override_info.key() == address, CustomError::MissingActionAccount);
+
+    let mut fee_override = FeeOverride::load(fee_override_info)?
+        .ok_or(CustomError::MissingActionAccount)?;
+
+    if clear_fee_override(&mut fee_override, timestamp) {
+        close_program_account(fee_override_info, rent_destination)
+    } else {
+        fee_override.try_serialize(&mut &mut fee_override_info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_fee_voucher(uses: u16, expires_at: i64, timestamp: i64) -> Result<()> {
+    require!(uses > 0, CustomError::InvalidAmount);
+    require!(expires_at > timestamp, CustomError::InvalidData);
+    require!(
+        uses <= FeeVoucher::MAX_USES
+            && expires_at - timestamp <= FeeVoucher::MAX_VALIDITY_SECONDS,
+        CustomError::FeeVoucherLimitExceeded
+    );
+    Ok(())
+}
+
+// Reissuing replaces the remaining uses and expiry but keeps the redemption history
+fn schedule_fee_voucher(
+    fee_voucher: &mut FeeVoucher,
+    issuer: Pubkey,
+    uses: u16,
+    expires_at: i64,
+    timestamp: i64,
+) -> Result<()> {
+    validate_fee_voucher(uses, expires_at, timestamp)?;
+
+    fee_voucher.issuer = issuer;
+    fee_voucher.remaining_uses = uses;
+    fee_voucher.expires_at = expires_at;
+    fee_voucher.created_at = timestamp;
+    Ok(())
+}
+
+fn emit_fee_voucher_issued(voucher: Pubkey, fee_voucher: &FeeVoucher) {
+    emit!(FeeVoucherIssued {
+        voucher,
+        beneficiary: fee_voucher.beneficiary,
+        issuer: fee_voucher.issuer,
+        uses: fee_voucher.remaining_uses,
+        expires_at: fee_voucher.expires_at,
+    });
+}
+
+// Multisig proposals cannot declare the voucher as `init_if_needed`, so it is created here
+pub(crate) fn apply_fee_voucher<'info>(
+    fee_voucher_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    artist_profile: Pubkey,
+    uses: u16,
+    expires_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let (address, bump) = Pubkey::find_program_address(
+        &[FEE_VOUCHER_SEED, artist_profile.as_ref()],
+        &crate::ID
+    );
+    require!(fee_voucher_info.key() == address, CustomError::MissingActionAccount);
+
+    let mut fee_voucher = match FeeVoucher::load(fee_voucher_info)? {
+        Some(fee_voucher) => fee_voucher,
+        None => {
+            create_program_account(
+                fee_voucher_info,
+                payer,
+                system_program,
+                &[FEE_VOUCHER_SEED, artist_profile.as_ref(), &[bump]],
+                FeeVoucher::INIT_SPACE,
+            )?;
+
+            FeeVoucher {
+                beneficiary: artist_profile,
+                issuer: payer.key(),
+                remaining_uses: 0,
+                redeemed_count: 0,
+                expires_at: 0,
+                created_at: clock.unix_timestamp,
+                bump,
+            }
+        }
+    };
+
+    schedule_fee_voucher(&mut fee_voucher, payer.key(), uses, expires_at, clock.unix_timestamp)?;
+    fee_voucher.try_serialize(&mut &mut fee_voucher_info.try_borrow_mut_data()?[..])?;
+    emit_fee_voucher_issued(address, &fee_voucher);
+
+    msg!("Fee voucher with {} uses issued to artist profile: {}", uses, artist_profile);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeOverrideAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeeOverride::INIT_SPACE,
+        seeds = [FEE_OVERRIDE_SEED, artist_profile.key().as_ref()],
+        bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFeeOverrideAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_OVERRIDE_SEED, fee_override.artist_profile.as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct IssueFeeVoucherAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeeVoucher::INIT_SPACE,
+        seeds = [FEE_VOUCHER_SEED, artist_profile.key().as_ref()],
+        bump
+    )]
+    pub fee_voucher: Account<'info, FeeVoucher>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeFeeVoucherAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [FEE_VOUCHER_SEED, fee_voucher.beneficiary.as_ref()],
+        bump = fee_voucher.bump
+    )]
+    pub fee_voucher: Account<'info, FeeVoucher>,
+
+    pub system_program: Program<'info, System>,
+}
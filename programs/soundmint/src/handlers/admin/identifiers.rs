@@ -0,0 +1,358 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::events::{FingerprintReassigned, IdentifierReassigned};
+use crate::handlers::collection::Collection;
+
+pub(crate) fn reassign_isrc(
+    context: Context<ReassignIsrcAccountConstraints>,
+    reason_code: u8
+) -> Result<()> {
+    let registration = &mut context.accounts.registration;
+    let current_master_nft = &mut context.accounts.current_master_nft;
+    let new_master_nft = &mut context.accounts.new_master_nft;
+    let moderator = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &moderator,
+            context.accounts.role.as_deref(),
+            Role::Moderator
+        ),
+        CustomError::Unauthorized
+    );
+    require!(new_master_nft.isrc.is_none(), CustomError::IdentifierAlreadySet);
+    
+    current_master_nft.isrc = None;
+    new_master_nft.isrc = Some(registration.code.clone());
+    registration.reassign(
+        new_master_nft.key(),
+        new_master_nft.artist_profile,
+        moderator,
+        reason_code,
+        clock.unix_timestamp,
+    );
+    
+    emit!(IdentifierReassigned {
+        registration: registration.key(),
+        kind: IdentifierKind::Isrc,
+        code: registration.code.clone(),
+        previous_holder: current_master_nft.key(),
+        new_holder: new_master_nft.key(),
+        moderator,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("ISRC {} reassigned to track: {}", registration.code, new_master_nft.title);
+    Ok(())
+}
+
+pub(crate) fn reassign_upc(
+    context: Context<ReassignUpcAccountConstraints>,
+    reason_code: u8
+) -> Result<()> {
+    let registration = &mut context.accounts.registration;
+    let current_collection = &mut context.accounts.current_collection;
+    let new_collection = &mut context.accounts.new_collection;
+    let moderator = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &moderator,
+            context.accounts.role.as_deref(),
+            Role::Moderator
+        ),
+        CustomError::Unauthorized
+    );
+    require!(new_collection.upc.is_none(), CustomError::IdentifierAlreadySet);
+    
+    current_collection.upc = None;
+    new_collection.upc = Some(registration.code.clone());
+    registration.reassign(
+        new_collection.key(),
+        new_collection.artist_profile,
+        moderator,
+        reason_code,
+        clock.unix_timestamp,
+    );
+    
+    emit!(IdentifierReassigned {
+        registration: registration.key(),
+        kind: IdentifierKind::Upc,
+        code: registration.code.clone(),
+        previous_holder: current_collection.key(),
+        new_holder: new_collection.key(),
+        moderator,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("UPC {} reassigned to collection: {}", registration.code, new_collection.name);
+    Ok(())
+}
+
+pub(crate) fn reassign_fingerprint(
+    context: Context<ReassignFingerprintAccountConstraints>,
+    reason_code: u8
+) -> Result<()> {
+    let audio_fingerprint = &mut context.accounts.audio_fingerprint;
+    let current_master_nft = &mut context.accounts.current_master_nft;
+    let new_master_nft = &mut context.accounts.new_master_nft;
+    let moderator = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &moderator,
+            context.accounts.role.as_deref(),
+            Role::Moderator
+        ),
+        CustomError::Unauthorized
+    );
+    require!(new_master_nft.fingerprint == [0; 32], CustomError::IdentifierAlreadySet);
+    
+    current_master_nft.fingerprint = [0; 32];
+    new_master_nft.fingerprint = audio_fingerprint.digest;
+    new_master_nft.version = None;
+    audio_fingerprint.reassign(
+        new_master_nft.key(),
+        new_master_nft.artist_profile,
+        moderator,
+        reason_code,
+        clock.unix_timestamp,
+    );
+    
+    emit!(FingerprintReassigned {
+        audio_fingerprint: audio_fingerprint.key(),
+        previous_holder: current_master_nft.key(),
+        new_holder: new_master_nft.key(),
+        moderator,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Recording reassigned to track: {}", new_master_nft.title);
+    Ok(())
+}
+
+// Drops a track's claim on a recording. The entry stays open so the rightful artist's mint
+// picks it up.
+pub(crate) fn revoke_fingerprint(
+    context: Context<RevokeFingerprintAccountConstraints>,
+    reason_code: u8
+) -> Result<()> {
+    let audio_fingerprint = &mut context.accounts.audio_fingerprint;
+    let master_nft = &mut context.accounts.master_nft;
+    let moderator = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &moderator,
+            context.accounts.role.as_deref(),
+            Role::Moderator
+        ),
+        CustomError::Unauthorized
+    );
+    require!(
+        audio_fingerprint.original == master_nft.key() || master_nft.version.is_some(),
+        CustomError::FingerprintAccountMismatch
+    );
+    
+    audio_fingerprint.revoke(&master_nft.key(), moderator, reason_code, clock.unix_timestamp);
+    master_nft.fingerprint = [0; 32];
+    master_nft.version = None;
+    
+    emit!(FingerprintReassigned {
+        audio_fingerprint: audio_fingerprint.key(),
+        previous_holder: master_nft.key(),
+        new_holder: Pubkey::default(),
+        moderator,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Recording claim revoked from track: {}", master_nft.title);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReassignIsrcAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            IDENTIFIER_SEED,
+            &IdentifierKind::Isrc.seed(),
+            &IdentifierRegistration::code_seed(&registration.code),
+        ],
+        bump = registration.bump
+    )]
+    pub registration: Box<Account<'info, IdentifierRegistration>>,
+    
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, current_master_nft.mint.as_ref()],
+        bump = current_master_nft.bump,
+        constraint = registration.holder == current_master_nft.key() @ CustomError::IdentifierAccountMismatch
+    )]
+    pub current_master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, new_master_nft.mint.as_ref()],
+        bump = new_master_nft.bump
+    )]
+    pub new_master_nft: Box<Account<'info, MasterNft>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReassignUpcAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            IDENTIFIER_SEED,
+            &IdentifierKind::Upc.seed(),
+            &IdentifierRegistration::code_seed(&registration.code),
+        ],
+        bump = registration.bump
+    )]
+    pub registration: Box<Account<'info, IdentifierRegistration>>,
+    
+    #[account(
+        mut,
+        seeds = [SOUND_MINT_COLLECTION_PREFIX, current_collection.mint.as_ref()],
+        bump = current_collection.bump,
+        constraint = registration.holder == current_collection.key() @ CustomError::IdentifierAccountMismatch
+    )]
+    pub current_collection: Box<Account<'info, Collection>>,
+    
+    #[account(
+        mut,
+        seeds = [SOUND_MINT_COLLECTION_PREFIX, new_collection.mint.as_ref()],
+        bump = new_collection.bump
+    )]
+    pub new_collection: Box<Account<'info, Collection>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReassignFingerprintAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    #[account(
+        mut,
+        seeds = [AUDIO_FINGERPRINT_SEED, audio_fingerprint.digest.as_ref()],
+        bump = audio_fingerprint.bump
+    )]
+    pub audio_fingerprint: Box<Account<'info, AudioFingerprint>>,
+    
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, current_master_nft.mint.as_ref()],
+        bump = current_master_nft.bump,
+        constraint = audio_fingerprint.original == current_master_nft.key() @ CustomError::FingerprintAccountMismatch
+    )]
+    pub current_master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, new_master_nft.mint.as_ref()],
+        bump = new_master_nft.bump
+    )]
+    pub new_master_nft: Box<Account<'info, MasterNft>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeFingerprintAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    #[account(
+        mut,
+        seeds = [AUDIO_FINGERPRINT_SEED, audio_fingerprint.digest.as_ref()],
+        bump = audio_fingerprint.bump
+    )]
+    pub audio_fingerprint: Box<Account<'info, AudioFingerprint>>,
+    
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump,
+        constraint = master_nft.fingerprint == audio_fingerprint.digest @ CustomError::FingerprintAccountMismatch
+    )]
+    pub master_nft: Box<Account<'info, MasterNft>>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::handlers::program_accounts::resize_program_account;
+
+// Accounts written by the first release are read with their legacy layout and rewritten in
+// the current one. The treasury goes first, since every other migration loads it.
+pub(crate) fn migrate_treasury(
+    context: Context<MigrateTreasuryAccountConstraints>
+) -> Result<()> {
+    let treasury_info = context.accounts.treasury.to_account_info();
+    let legacy: LegacyTreasury = read_legacy_account(&treasury_info, &Treasury::DISCRIMINATOR, Treasury::INIT_SPACE)?;
+    require!(legacy.authority == context.accounts.authority.key(), CustomError::Unauthorized);
+
+    write_migrated_account(
+        &treasury_info,
+        &context.accounts.authority.to_account_info(),
+        &context.accounts.system_program.to_account_info(),
+        Treasury::INIT_SPACE,
+        &legacy.upgrade(),
+    )?;
+
+    msg!("Treasury migrated to the current layout");
+    Ok(())
+}
+
+pub(crate) fn migrate_artist_profile(
+    context: Context<MigrateArtistProfileAccountConstraints>
+) -> Result<()> {
+    let artist_profile_info = context.accounts.artist_profile.to_account_info();
+    let legacy: LegacyArtistProfile = read_legacy_account(
+        &artist_profile_info,
+        &ArtistProfile::DISCRIMINATOR,
+        ArtistProfile::INIT_SPACE,
+    )?;
+
+    // Legacy profiles were seeded by their authority, which becomes the creator
+    let address = Pubkey::create_program_address(
+        &[ARTIST_PROFILE_SEED, legacy.authority.as_ref(), &[legacy.bump]],
+        &crate::ID
+    ).map_err(|_| CustomError::InvalidData)?;
+    require!(artist_profile_info.key() == address, CustomError::InvalidData);
+
+    let artist_profile = legacy.upgrade();
+    write_migrated_account(
+        &artist_profile_info,
+        &context.accounts.authority.to_account_info(),
+        &context.accounts.system_program.to_account_info(),
+        ArtistProfile::INIT_SPACE,
+        &artist_profile,
+    )?;
+
+    msg!("Artist profile migrated to the current layout: {}", artist_profile.name);
+    Ok(())
+}
+
+pub(crate) fn migrate_master_nft(
+    context: Context<MigrateMasterNftAccountConstraints>
+) -> Result<()> {
+    let master_nft_info = context.accounts.master_nft.to_account_info();
+    let legacy: LegacyMasterNft = read_legacy_account(
+        &master_nft_info,
+        &MasterNft::DISCRIMINATOR,
+        MasterNft::INIT_SPACE,
+    )?;
+
+    let address = Pubkey::create_program_address(
+        &[MASTER_NFT_SEED, legacy.mint.as_ref(), &[legacy.bump]],
+        &crate::ID
+    ).map_err(|_| CustomError::InvalidData)?;
+    require!(master_nft_info.key() == address, CustomError::InvalidData);
+
+    let master_nft = legacy.upgrade();
+    write_migrated_account(
+        &master_nft_info,
+        &context.accounts.authority.to_account_info(),
+        &context.accounts.system_program.to_account_info(),
+        MasterNft::INIT_SPACE,
+        &master_nft,
+    )?;
+
+    msg!("Master NFT migrated to the current layout: {}", master_nft.title);
+    Ok(())
+}
+
+// Helper Functions
+// Accounts already at the current size were written with the current layout
+fn read_legacy_account<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    current_space: usize,
+) -> Result<T> {
+    require!(account.owner == &crate::ID, CustomError::InvalidData);
+    require!(account.data_len() < current_space, CustomError::AccountAlreadyMigrated);
+
+    let data = account.try_borrow_data()?;
+    require!(data.starts_with(discriminator), CustomError::InvalidData);
+    Ok(T::deserialize(&mut &data[discriminator.len()..])?)
+}
+
+// Rewrites the whole account, so nothing a longer legacy write left past its fields survives
+fn write_migrated_account<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    migrated: &T,
+) -> Result<()> {
+    resize_program_account(account, payer, system_program, space)?;
+    migrated.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasuryAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Treasury in its legacy layout, authority checked in the handler
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateArtistProfileAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Artist profile in its legacy layout, address checked in the handler
+    #[account(mut)]
+    pub artist_profile: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMasterNftAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Master NFT in its legacy layout, address checked in the handler
+    #[account(mut)]
+    pub master_nft: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
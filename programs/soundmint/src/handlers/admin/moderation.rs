@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::events::MasterNftModerated;
+use crate::handlers::nft::set_token_frozen;
+
+pub(crate) fn moderate_master_nft(
+    context: Context<ModerateMasterNftAccountConstraints>,
+    status: MasterNftStatus,
+    reason_code: u8
+) -> Result<()> {
+    let master_nft = &mut context.accounts.master_nft;
+    let moderator = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        AdminRole::has_access(
+            &context.accounts.treasury,
+            &moderator,
+            context.accounts.role.as_deref(),
+            Role::Moderator
+        ),
+        CustomError::Unauthorized
+    );
+    require!(
+        master_nft.status.can_moderator_transition(status),
+        CustomError::InvalidStatusTransition
+    );
+    
+    master_nft.status = status;
+    master_nft.moderation_reason = reason_code;
+    master_nft.moderated_at = clock.unix_timestamp;
+    
+    // Locked tracks cannot change hands until the moderator lifts the lock
+    set_token_frozen(
+        &context.accounts.token_program,
+        &context.accounts.token_account,
+        &context.accounts.mint,
+        &context.accounts.nft_authority,
+        context.bumps.nft_authority,
+        master_nft.is_locked(),
+    )?;
+    
+    emit!(MasterNftModerated {
+        master_nft: master_nft.key(),
+        moderator,
+        status,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Master NFT {} moderated to {:?} (reason {})", master_nft.title, status, reason_code);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ModerateMasterNftAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump
+    )]
+    pub master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        constraint = master_nft.mint == mint.key() @ CustomError::InvalidData
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    
+    // Current holder's token account
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ CustomError::InvalidData,
+        constraint = token_account.amount == 1 @ CustomError::InvalidData
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Program PDA holding the mint's freeze authority
+    #[account(
+        seeds = [NFT_AUTHORITY_SEED],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,477 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use super::artist::apply_artist_verification;
+use super::fees::{
+    apply_fee_override,
+    apply_fee_voucher,
+    apply_remove_fee_override,
+    validate_fee_override,
+    validate_fee_voucher,
+};
+use super::pause::apply_guardian;
+use super::roles::{apply_grant_role, apply_revoke_role};
+use super::treasury_management::{
+    apply_pending_authority,
+    apply_streaming_provider,
+    clear_pending_authority,
+    clear_pending_config,
+    schedule_referral_fee,
+    schedule_treasury_config,
+    transfer_treasury_funds,
+};
+
+pub(crate) fn configure_admin_multisig(
+    context: Context<ConfigureAdminMultisigAccountConstraints>,
+    admins: Vec<Pubkey>,
+    threshold: u8
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+
+    // Once enabled, the admin set can only be changed by a SetAdmins proposal
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    apply_admin_set(treasury, admins, threshold, clock.unix_timestamp)
+}
+
+pub(crate) fn propose_admin_action(
+    context: Context<ProposeAdminActionAccountConstraints>,
+    action: AdminAction
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let proposal = &mut context.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(treasury.is_multisig_enabled(), CustomError::MultisigNotEnabled);
+    require!(treasury.is_admin(&context.accounts.proposer.key()), CustomError::NotAnAdmin);
+    validate_admin_action(&action, clock.unix_timestamp)?;
+
+    proposal.treasury = treasury.key();
+    proposal.index = treasury.proposal_count;
+    proposal.proposer = context.accounts.proposer.key();
+    proposal.action = action;
+    proposal.approvals = vec![context.accounts.proposer.key()];
+    proposal.executed = false;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.executed_at = 0;
+    proposal.bump = context.bumps.proposal;
+    proposal.expires_at = clock.unix_timestamp.checked_add(ADMIN_PROPOSAL_LIFETIME_SECONDS).unwrap();
+
+    treasury.proposal_count = treasury.proposal_count.checked_add(1).unwrap();
+
+    msg!("Admin proposal {} created", proposal.index);
+    Ok(())
+}
+
+pub(crate) fn approve_admin_proposal(
+    context: Context<ApproveAdminProposalAccountConstraints>
+) -> Result<()> {
+    let treasury = &context.accounts.treasury;
+    let proposal = &mut context.accounts.proposal;
+    let admin = context.accounts.admin.key();
+    let clock = Clock::get()?;
+
+    require!(treasury.is_admin(&admin), CustomError::NotAnAdmin);
+    require!(!proposal.executed, CustomError::ProposalAlreadyExecuted);
+    require!(!proposal.is_expired(clock.unix_timestamp), CustomError::ProposalExpired);
+    require!(!proposal.approvals.contains(&admin), CustomError::AlreadyApproved);
+
+    proposal.approvals.push(admin);
+
+    msg!(
+        "Admin proposal {} approved ({}/{})",
+        proposal.index,
+        proposal.approval_count(treasury),
+        treasury.admin_threshold
+    );
+    Ok(())
+}
+
+pub(crate) fn execute_admin_proposal(
+    context: Context<ExecuteAdminProposalAccountConstraints>
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = context.accounts;
+
+    require!(accounts.treasury.is_admin(&accounts.admin.key()), CustomError::NotAnAdmin);
+    require!(!accounts.proposal.executed, CustomError::ProposalAlreadyExecuted);
+    require!(!accounts.proposal.is_expired(clock.unix_timestamp), CustomError::ProposalExpired);
+    require!(
+        accounts.proposal.approval_count(&accounts.treasury) >= accounts.treasury.admin_threshold as usize,
+        CustomError::InsufficientApprovals
+    );
+
+    let treasury = &mut accounts.treasury;
+    treasury.apply_pending_config(clock.unix_timestamp);
+
+    match accounts.proposal.action.clone() {
+        AdminAction::UpdateTreasuryConfig {
+            mint_fee,
+            platform_fee_basis_points,
+            verified_mint_fee,
+            verified_platform_fee_basis_points,
+            new_treasury_wallet,
+        } => schedule_treasury_config(
+            treasury,
+            mint_fee,
+            platform_fee_basis_points,
+            verified_mint_fee,
+            verified_platform_fee_basis_points,
+            new_treasury_wallet,
+            clock.unix_timestamp,
+        )?,
+        AdminAction::UpdateStreamingProvider { new_streaming_provider } => {
+            apply_streaming_provider(treasury, new_streaming_provider, clock.unix_timestamp)?
+        }
+        AdminAction::WithdrawTreasuryFunds { category, amount } => {
+            let treasury_wallet = accounts
+                .treasury_wallet
+                .as_ref()
+                .ok_or(CustomError::MissingActionAccount)?;
+
+            transfer_treasury_funds(treasury, treasury_wallet, category, amount)?
+        }
+        AdminAction::VerifyArtist { artist_profile, verify } => {
+            let artist_profile_account = accounts
+                .artist_profile
+                .as_mut()
+                .ok_or(CustomError::MissingActionAccount)?;
+            require!(
+                artist_profile_account.key() == artist_profile,
+                CustomError::MissingActionAccount
+            );
+
+            apply_artist_verification(artist_profile_account, verify)?
+        }
+        AdminAction::SetAdmins { admins, threshold } => {
+            apply_admin_set(treasury, admins, threshold, clock.unix_timestamp)?
+        }
+        AdminAction::ProposeTreasuryAuthority { new_authority } => {
+            apply_pending_authority(treasury, new_authority, clock.unix_timestamp)?
+        }
+        AdminAction::CancelTreasuryAuthorityTransfer => {
+            clear_pending_authority(treasury, clock.unix_timestamp)?
+        }
+        AdminAction::CancelTreasuryConfig => {
+            clear_pending_config(treasury, clock.unix_timestamp)?
+        }
+        AdminAction::SetGuardian { new_guardian } => {
+            apply_guardian(treasury, new_guardian, clock.unix_timestamp)?
+        }
+        AdminAction::GrantRole { holder, role } => {
+            let admin_role = accounts
+                .admin_role
+                .as_ref()
+                .ok_or(CustomError::MissingActionAccount)?;
+
+            apply_grant_role(
+                admin_role,
+                &accounts.admin.to_account_info(),
+                &accounts.system_program.to_account_info(),
+                holder,
+                role,
+                clock.unix_timestamp,
+            )?
+        }
+        AdminAction::RevokeRole { holder, role } => {
+            let admin_role = accounts
+                .admin_role
+                .as_ref()
+                .ok_or(CustomError::MissingActionAccount)?;
+
+            apply_revoke_role(admin_role, &accounts.admin.to_account_info(), holder, role)?
+        }
+        AdminAction::SetReferralFee { referral_fee_basis_points } => {
+            schedule_referral_fee(treasury, referral_fee_basis_points, clock.unix_timestamp)?
+        }
+        AdminAction::SetFeeOverride { artist_profile, mint_fee, platform_fee_basis_points, note } => {
+            let fee_override = accounts
+                .fee_override
+                .as_ref()
+                .ok_or(CustomError::MissingActionAccount)?;
+
+            apply_fee_override(
+                fee_override,
+                &accounts.admin.to_account_info(),
+                &accounts.system_program.to_account_info(),
+                artist_profile,
+                mint_fee,
+                platform_fee_basis_points,
+                note,
+            )?
+        }
+        AdminAction::RemoveFeeOverride { artist_profile } => {
+            let fee_override = accounts
+                .fee_override
+                .as_ref()
+                .ok_or(CustomError::MissingActionAccount)?;
+
+            apply_remove_fee_override(
+                fee_override,
+                &accounts.admin.to_account_info(),
+                artist_profile,
+                clock.unix_timestamp,
+            )?
+        }
+        AdminAction::IssueFeeVoucher { artist_profile, uses, expires_at } => {
+            let fee_voucher = accounts
+                .fee_voucher
+                .as_ref()
+                .ok_or(CustomError::MissingActionAccount)?;
+
+            apply_fee_voucher(
+                fee_voucher,
+                &accounts.admin.to_account_info(),
+                &accounts.system_program.to_account_info(),
+                artist_profile,
+                uses,
+                expires_at,
+            )?
+        }
+    }
+
+    let proposal = &mut accounts.proposal;
+    proposal.executed = true;
+    proposal.executed_at = clock.unix_timestamp;
+
+    msg!("Admin proposal {} executed", proposal.index);
+    Ok(())
+}
+
+// Settled proposals hand their rent back to the proposer who paid for them
+pub(crate) fn close_admin_proposal(
+    context: Context<CloseAdminProposalAccountConstraints>
+) -> Result<()> {
+    let treasury = &context.accounts.treasury;
+    let proposal = &context.accounts.proposal;
+    let authority = context.accounts.authority.key();
+    let clock = Clock::get()?;
+
+    require!(
+        authority == proposal.proposer || treasury.is_admin(&authority),
+        CustomError::NotAnAdmin
+    );
+    require!(
+        proposal.executed || proposal.is_expired(clock.unix_timestamp),
+        CustomError::ProposalStillOpen
+    );
+
+    msg!("Admin proposal {} closed", proposal.index);
+    Ok(())
+}
+
+// Helper Functions
+fn validate_admin_action(action: &AdminAction, timestamp: i64) -> Result<()> {
+    match action {
+        AdminAction::UpdateTreasuryConfig {
+            platform_fee_basis_points,
+            verified_platform_fee_basis_points,
+            ..
+        } => {
+            for fee_basis_points in [platform_fee_basis_points, verified_platform_fee_basis_points].into_iter().flatten() {
+                require!(
+                    *fee_basis_points <= TOTAL_BASIS_POINTS,
+                    CustomError::InvalidFeePercentage
+                );
+            }
+        }
+        AdminAction::SetReferralFee { referral_fee_basis_points } => {
+            require!(
+                *referral_fee_basis_points <= TOTAL_BASIS_POINTS,
+                CustomError::InvalidFeePercentage
+            );
+        }
+        AdminAction::SetFeeOverride { mint_fee, platform_fee_basis_points, note, .. } => {
+            validate_fee_override(*mint_fee, *platform_fee_basis_points, note)?;
+        }
+        AdminAction::IssueFeeVoucher { uses, expires_at, .. } => {
+            validate_fee_voucher(*uses, *expires_at, timestamp)?;
+        }
+        AdminAction::WithdrawTreasuryFunds { amount, .. } => {
+            require!(*amount > 0, CustomError::InvalidAmount);
+        }
+        AdminAction::SetAdmins { admins, threshold } => {
+            validate_admin_set(admins, *threshold)?;
+        }
+        AdminAction::ProposeTreasuryAuthority { new_authority }
+        | AdminAction::SetGuardian { new_guardian: new_authority }
+        | AdminAction::GrantRole { holder: new_authority, .. } => {
+            require!(*new_authority != Pubkey::default(), CustomError::InvalidAuthority);
+        }
+        AdminAction::UpdateStreamingProvider { .. }
+        | AdminAction::VerifyArtist { .. }
+        | AdminAction::RevokeRole { .. }
+        | AdminAction::RemoveFeeOverride { .. }
+        | AdminAction::CancelTreasuryAuthorityTransfer
+        | AdminAction::CancelTreasuryConfig => {}
+    }
+    Ok(())
+}
+
+fn validate_admin_set(admins: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !admins.is_empty() && admins.len() <= Treasury::MAX_ADMINS,
+        CustomError::InvalidAdminSet
+    );
+    require!(
+        threshold > 0 && threshold as usize <= admins.len(),
+        CustomError::InvalidAdminSet
+    );
+
+    for (index, admin) in admins.iter().enumerate() {
+        require!(!admins[..index].contains(admin), CustomError::InvalidAdminSet);
+    }
+
+    Ok(())
+}
+
+fn apply_admin_set(
+    treasury: &mut Treasury,
+    admins: Vec<Pubkey>,
+    threshold: u8,
+    timestamp: i64,
+) -> Result<()> {
+    validate_admin_set(&admins, threshold)?;
+
+    treasury.admins = admins;
+    treasury.admin_threshold = threshold;
+    treasury.updated_at = timestamp;
+
+    msg!(
+        "Admin multisig set to {}-of-{}",
+        treasury.admin_threshold,
+        treasury.admins.len()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureAdminMultisigAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminActionAccountConstraints<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = AdminProposal::INIT_SPACE,
+        seeds = [ADMIN_PROPOSAL_SEED, treasury.key().as_ref(), &treasury.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminProposalAccountConstraints<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.treasury == treasury.key() @ CustomError::InvalidData
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminProposalAccountConstraints<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.treasury == treasury.key() @ CustomError::InvalidData
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+
+    /// CHECK: Treasury wallet to receive funds (WithdrawTreasuryFunds only)
+    #[account(mut)]
+    pub treasury_wallet: Option<UncheckedAccount<'info>>,
+
+    // Artist being verified (VerifyArtist only)
+    #[account(mut)]
+    pub artist_profile: Option<Box<Account<'info, ArtistProfile>>>,
+
+    /// CHECK: Role account to create or close (GrantRole and RevokeRole only), address checked on execution
+    #[account(mut)]
+    pub admin_role: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Fee override to create, update or close (SetFeeOverride and RemoveFeeOverride only), address checked on execution
+    #[account(mut)]
+    pub fee_override: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Fee voucher to create or reissue (IssueFeeVoucher only), address checked on execution
+    #[account(mut)]
+    pub fee_voucher: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAdminProposalAccountConstraints<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_PROPOSAL_SEED, treasury.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.treasury == treasury.key() @ CustomError::InvalidData,
+        close = proposer
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+
+    /// CHECK: Proposer who paid for the proposal and receives its rent back
+    #[account(
+        mut,
+        address = proposal.proposer @ CustomError::InvalidData
+    )]
+    pub proposer: UncheckedAccount<'info>,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+
+pub(crate) fn set_pause_flags(
+    context: Context<SetPauseFlagsAccountConstraints>,
+    paused_flags: u8
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+
+    require!(paused_flags & !PAUSE_ALL == 0, CustomError::InvalidData);
+
+    treasury.paused_flags = paused_flags;
+    treasury.updated_at = clock.unix_timestamp;
+
+    msg!("Pause flags set to {:#08b}", paused_flags);
+    Ok(())
+}
+
+pub(crate) fn set_guardian(
+    context: Context<SetGuardianAccountConstraints>,
+    new_guardian: Pubkey
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    apply_guardian(treasury, new_guardian, clock.unix_timestamp)
+}
+
+// Shared by the direct admin instruction and multisig proposal execution
+pub(crate) fn apply_guardian(
+    treasury: &mut Treasury,
+    new_guardian: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    require!(new_guardian != Pubkey::default(), CustomError::InvalidAuthority);
+
+    treasury.guardian = new_guardian;
+    treasury.updated_at = timestamp;
+
+    msg!("Guardian updated to: {}", new_guardian);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPauseFlagsAccountConstraints<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.guardian == guardian.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardianAccountConstraints<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
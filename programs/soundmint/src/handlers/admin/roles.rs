@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::handlers::program_accounts::{close_program_account, create_program_account};
+
+pub(crate) fn grant_role(
+    context: Context<GrantRoleAccountConstraints>,
+    role: Role
+) -> Result<()> {
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    let admin_role = &mut context.accounts.admin_role;
+    let clock = Clock::get()?;
+
+    admin_role.holder = context.accounts.holder.key();
+    admin_role.role = role;
+    admin_role.granted_by = context.accounts.authority.key();
+    admin_role.granted_at = clock.unix_timestamp;
+    admin_role.bump = context.bumps.admin_role;
+
+    msg!("Granted {:?} role to {}", role, admin_role.holder);
+    Ok(())
+}
+
+pub(crate) fn revoke_role(
+    context: Context<RevokeRoleAccountConstraints>
+) -> Result<()> {
+    require!(!context.accounts.treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+
+    msg!(
+        "Revoked {:?} role from {}",
+        context.accounts.admin_role.role,
+        context.accounts.admin_role.holder
+    );
+    Ok(())
+}
+
+// Multisig proposals cannot declare the role account as `init`, so it is created here
+pub(crate) fn apply_grant_role<'info>(
+    admin_role: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    holder: Pubkey,
+    role: Role,
+    timestamp: i64,
+) -> Result<()> {
+    let (address, bump) = Pubkey::find_program_address(
+        &[ADMIN_ROLE_SEED, holder.as_ref(), &role.seed()],
+        &crate::ID
+    );
+    require!(admin_role.key() == address, CustomError::MissingActionAccount);
+
+    create_program_account(
+        admin_role,
+        payer,
+        system_program,
+        &[ADMIN_ROLE_SEED, holder.as_ref(), &role.seed(), &[bump]],
+        AdminRole::INIT_SPACE,
+    )?;
+
+    let granted = AdminRole {
+        holder,
+        role,
+        granted_by: payer.key(),
+        granted_at: timestamp,
+        bump,
+    };
+    granted.try_serialize(&mut &mut admin_role.try_borrow_mut_data()?[..])?;
+
+    msg!("Granted {:?} role to {}", role, holder);
+    Ok(())
+}
+
+pub(crate) fn apply_revoke_role<'info>(
+    admin_role: &AccountInfo<'info>,
+    rent_destination: &AccountInfo<'info>,
+    holder: Pubkey,
+    role: Role,
+) -> Result<()> {
+    let address = Pubkey::find_program_address(
+        &[ADMIN_ROLE_SEED, holder.as_ref(), &role.seed()],
+        &crate::ID
+    ).0;
+    require!(
+        admin_role.key() == address && admin_role.owner == &crate::ID,
+        CustomError::MissingActionAccount
+    );
+
+    close_program_account(admin_role, rent_destination)?;
+
+    msg!("Revoked {:?} role from {}", role, holder);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role)]
+pub struct GrantRoleAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Wallet receiving the role
+    pub holder: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AdminRole::INIT_SPACE,
+        seeds = [ADMIN_ROLE_SEED, holder.key().as_ref(), &role.seed()],
+        bump
+    )]
+    pub admin_role: Account<'info, AdminRole>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRoleAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ADMIN_ROLE_SEED, admin_role.holder.as_ref(), &admin_role.role.seed()],
+        bump = admin_role.bump
+    )]
+    pub admin_role: Account<'info, AdminRole>,
+
+    pub system_program: Program<'info, System>,
+}
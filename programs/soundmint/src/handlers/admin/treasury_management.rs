@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+
+pub(crate) fn update_treasury_config(
+    context: Context<UpdateTreasuryConfigAccountConstraints>,
+    mint_fee: Option<u64>,
+    platform_fee_basis_points: Option<u16>,
+    verified_mint_fee: Option<u64>,
+    verified_platform_fee_basis_points: Option<u16>,
+    new_treasury_wallet: Option<Pubkey>
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    // Only the authority or a fee manager can change fees
+    require!(
+        AdminRole::has_access(
+            treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    schedule_treasury_config(
+        treasury,
+        mint_fee,
+        platform_fee_basis_points,
+        verified_mint_fee,
+        verified_platform_fee_basis_points,
+        new_treasury_wallet,
+        clock.unix_timestamp,
+    )
+}
+
+pub(crate) fn cancel_treasury_config(
+    context: Context<UpdateTreasuryConfigAccountConstraints>
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    // Only the authority or a fee manager can change fees
+    require!(
+        AdminRole::has_access(
+            treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::FeeManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    clear_pending_config(treasury, clock.unix_timestamp)
+}
+
+pub(crate) fn update_streaming_provider(
+    context: Context<UpdateTreasuryConfigAccountConstraints>,
+    new_streaming_provider: Pubkey
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    // Only the authority or a streaming manager can swap providers
+    require!(
+        AdminRole::has_access(
+            treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::StreamingManager
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    apply_streaming_provider(treasury, new_streaming_provider, clock.unix_timestamp)
+}
+
+pub(crate) fn withdraw_treasury_funds(
+    context: Context<WithdrawTreasuryFundsAccountConstraints>,
+    category: FeeCategory,
+    amount: u64
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    // Only allow withdrawals by treasury authority or a withdrawer
+    require!(
+        AdminRole::has_access(
+            treasury,
+            &context.accounts.authority.key(),
+            context.accounts.role.as_deref(),
+            Role::Withdrawer
+        ),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    treasury.apply_pending_config(clock.unix_timestamp);
+    
+    transfer_treasury_funds(
+        treasury,
+        &context.accounts.treasury_wallet,
+        category,
+        amount,
+    )
+}
+
+pub(crate) fn propose_treasury_authority(
+    context: Context<UpdateTreasuryConfigAccountConstraints>,
+    new_authority: Pubkey
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    require!(
+        treasury.authority == context.accounts.authority.key(),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    apply_pending_authority(treasury, new_authority, clock.unix_timestamp)
+}
+
+pub(crate) fn accept_treasury_authority(
+    context: Context<AcceptTreasuryAuthorityAccountConstraints>
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    // The incoming key must sign, which rules out handing control to a mistyped address
+    require!(
+        treasury.pending_authority == Some(context.accounts.new_authority.key()),
+        CustomError::NoPendingAuthority
+    );
+    
+    let previous_authority = treasury.authority;
+    treasury.authority = context.accounts.new_authority.key();
+    treasury.pending_authority = None;
+    treasury.updated_at = clock.unix_timestamp;
+    
+    msg!("Treasury authority transferred from {} to {}", previous_authority, treasury.authority);
+    Ok(())
+}
+
+pub(crate) fn cancel_treasury_authority_transfer(
+    context: Context<UpdateTreasuryConfigAccountConstraints>
+) -> Result<()> {
+    let treasury = &mut context.accounts.treasury;
+    let clock = Clock::get()?;
+    
+    require!(
+        treasury.authority == context.accounts.authority.key(),
+        CustomError::Unauthorized
+    );
+    require!(!treasury.is_multisig_enabled(), CustomError::MultisigRequired);
+    
+    clear_pending_authority(treasury, clock.unix_timestamp)
+}
+
+// Shared by the direct admin instructions and multisig proposal execution
+pub(crate) fn schedule_treasury_config(
+    treasury: &mut Treasury,
+    mint_fee: Option<u64>,
+    platform_fee_basis_points: Option<u16>,
+    verified_mint_fee: Option<u64>,
+    verified_platform_fee_basis_points: Option<u16>,
+    new_treasury_wallet: Option<Pubkey>,
+    timestamp: i64,
+) -> Result<()> {
+    schedule_pending_config(
+        treasury,
+        PendingTreasuryConfig {
+            mint_fee,
+            platform_fee_basis_points,
+            verified_mint_fee,
+            verified_platform_fee_basis_points,
+            referral_fee_basis_points: None,
+            treasury_wallet: new_treasury_wallet,
+            effective_at: 0,
+        },
+        timestamp,
+    )
+}
+
+pub(crate) fn schedule_referral_fee(
+    treasury: &mut Treasury,
+    referral_fee_basis_points: u16,
+    timestamp: i64,
+) -> Result<()> {
+    schedule_pending_config(
+        treasury,
+        PendingTreasuryConfig {
+            mint_fee: None,
+            platform_fee_basis_points: None,
+            verified_mint_fee: None,
+            verified_platform_fee_basis_points: None,
+            referral_fee_basis_points: Some(referral_fee_basis_points),
+            treasury_wallet: None,
+            effective_at: 0,
+        },
+        timestamp,
+    )
+}
+
+// `effective_at` is ignored and replaced by the end of the timelock
+fn schedule_pending_config(
+    treasury: &mut Treasury,
+    config: PendingTreasuryConfig,
+    timestamp: i64,
+) -> Result<()> {
+    require!(
+        config.mint_fee.is_some()
+            || config.platform_fee_basis_points.is_some()
+            || config.verified_mint_fee.is_some()
+            || config.verified_platform_fee_basis_points.is_some()
+            || config.referral_fee_basis_points.is_some()
+            || config.treasury_wallet.is_some(),
+        CustomError::InvalidData
+    );
+    
+    for fee_basis_points in [
+        config.platform_fee_basis_points,
+        config.verified_platform_fee_basis_points,
+        config.referral_fee_basis_points,
+    ].into_iter().flatten() {
+        require!(
+            fee_basis_points <= TOTAL_BASIS_POINTS,
+            CustomError::InvalidFeePercentage
+        );
+    }
+    
+    // Land a matured change first so rescheduling never discards it
+    treasury.apply_pending_config(timestamp);
+    
+    let effective_at = timestamp.checked_add(CONFIG_TIMELOCK_SECONDS).unwrap();
+    treasury.pending_config = Some(PendingTreasuryConfig { effective_at, ..config });
+    treasury.updated_at = timestamp;
+    
+    msg!("Treasury configuration change scheduled for {}", effective_at);
+    Ok(())
+}
+
+pub(crate) fn clear_pending_config(
+    treasury: &mut Treasury,
+    timestamp: i64,
+) -> Result<()> {
+    // A change that already took effect can no longer be cancelled
+    treasury.apply_pending_config(timestamp);
+    require!(treasury.pending_config.is_some(), CustomError::NoPendingConfig);
+    
+    treasury.pending_config = None;
+    treasury.updated_at = timestamp;
+    
+    msg!("Pending treasury configuration change cancelled");
+    Ok(())
+}
+
+pub(crate) fn apply_streaming_provider(
+    treasury: &mut Treasury,
+    new_streaming_provider: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    treasury.streaming_provider = new_streaming_provider;
+    treasury.updated_at = timestamp;
+    
+    msg!("Streaming provider updated to: {}", new_streaming_provider);
+    Ok(())
+}
+
+pub(crate) fn apply_pending_authority(
+    treasury: &mut Treasury,
+    new_authority: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    require!(
+        new_authority != Pubkey::default() && new_authority != treasury.authority,
+        CustomError::InvalidAuthority
+    );
+    
+    treasury.pending_authority = Some(new_authority);
+    treasury.updated_at = timestamp;
+    
+    msg!("Treasury authority transfer proposed to: {}", new_authority);
+    Ok(())
+}
+
+pub(crate) fn clear_pending_authority(
+    treasury: &mut Treasury,
+    timestamp: i64,
+) -> Result<()> {
+    require!(treasury.pending_authority.is_some(), CustomError::NoPendingAuthority);
+    
+    treasury.pending_authority = None;
+    treasury.updated_at = timestamp;
+    
+    msg!("Treasury authority transfer cancelled");
+    Ok(())
+}
+
+pub(crate) fn transfer_treasury_funds<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    treasury_wallet: &AccountInfo<'info>,
+    category: FeeCategory,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, CustomError::InvalidAmount);
+    
+    // Check that the wallet receiving funds is the treasury wallet
+    require!(
+        treasury_wallet.key() == treasury.treasury_wallet,
+        CustomError::InvalidTreasuryWallet
+    );
+    
+    // Only fees booked under this category and not yet withdrawn can leave the vault
+    require!(
+        treasury.fee_ledger_mut(category).available() >= amount,
+        CustomError::InsufficientFunds
+    );
+    
+    // The treasury PDA must stay rent exempt
+    let treasury_info = treasury.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let withdrawable = treasury_info.lamports().saturating_sub(rent_exempt_minimum);
+    require!(withdrawable >= amount, CustomError::InsufficientFunds);
+    
+    // The treasury PDA carries data, so lamports are moved directly rather than via the system program
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **treasury_wallet.try_borrow_mut_lamports()? += amount;
+    
+    let ledger = treasury.fee_ledger_mut(category);
+    ledger.withdrawn = ledger.withdrawn.checked_add(amount).unwrap();
+    
+    msg!("Withdrawn {} lamports of {:?} fees from treasury", amount, category);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasuryConfigAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTreasuryAuthorityAccountConstraints<'info> {
+    #[account(mut)]
+    pub new_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.pending_authority == Some(new_authority.key()) @ CustomError::NoPendingAuthority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryFundsAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    // Signer's admin role, when acting as a role holder rather than the authority
+    #[account(
+        seeds = [ADMIN_ROLE_SEED, authority.key().as_ref(), &role.role.seed()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, AdminRole>>,
+    
+    /// CHECK: Treasury wallet to receive funds
+    #[account(
+        mut,
+        constraint = treasury_wallet.key() == treasury.effective_treasury_wallet(Clock::get()?.unix_timestamp) @ CustomError::InvalidTreasuryWallet
+    )]
+    pub treasury_wallet: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
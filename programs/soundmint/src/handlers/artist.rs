@@ -1,31 +1,57 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::CustomError;
+use crate::constants::*;
+use crate::events::VerificationRequested;
+use crate::handlers::program_accounts::close_program_account;
 
-pub fn create_artist_profile(context: Context<CreateArtistProfileAccountConstraints>, name: String, description: String, profile_image_uri: String) -> Result<()> {
+pub(crate) fn create_artist_profile(context: Context<CreateArtistProfileAccountConstraints>, name: String, description: String, profile_image_uri: String, referrer: Option<Pubkey>) -> Result<()> {
     require!(name.len() <= ArtistProfile::MAX_NAME_LENGTH, CustomError::StringTooLong);
     require!(description.len() <= ArtistProfile::MAX_DESCRIPTION_LENGTH, CustomError::StringTooLong);
     require!(profile_image_uri.len() <= ArtistProfile::MAX_URI_LENGTH, CustomError::StringTooLong);
     
+    // The referral link is fixed at onboarding and an artist cannot refer themselves
+    if let Some(referrer_key) = referrer {
+        require!(
+            referrer_key != Pubkey::default() && referrer_key != context.accounts.authority.key(),
+            CustomError::InvalidReferrer
+        );
+    }
+    
     let artist_profile = &mut context.accounts.artist_profile;
     let clock = Clock::get()?;
     
     artist_profile.authority = context.accounts.authority.key();
+    artist_profile.creator = context.accounts.authority.key();
+    artist_profile.pending_authority = None;
     artist_profile.name = name;
     artist_profile.description = description;
     artist_profile.profile_image_uri = profile_image_uri;
     artist_profile.social_links = Vec::new();
     artist_profile.is_verified = false;
+    artist_profile.verification_tier = VerificationTier::Unverified;
+    artist_profile.verified_at = 0;
     artist_profile.track_count = 0;
+    artist_profile.referrer = referrer;
+    artist_profile.label = None;
     artist_profile.created_at = clock.unix_timestamp;
     artist_profile.updated_at = clock.unix_timestamp;
     artist_profile.bump = context.bumps.artist_profile;
+    artist_profile.delegate_generation = 0;
+    
+    // Claim the normalized handle so no other wallet can register the same name
+    context.accounts.artist_handle.claim(
+        &artist_profile.name,
+        artist_profile.key(),
+        context.bumps.artist_handle,
+        clock.unix_timestamp,
+    )?;
     
     msg!("Artist profile created for: {}", artist_profile.name);
     Ok(())
 }
 
-pub fn update_artist_profile(
+pub(crate) fn update_artist_profile(
     context: Context<UpdateArtistProfileAccountConstraints>, 
     name: Option<String>, 
     description: Option<String>, 
@@ -37,6 +63,32 @@ pub fn update_artist_profile(
     
     if let Some(new_name) = name {
         require!(new_name.len() <= ArtistProfile::MAX_NAME_LENGTH, CustomError::StringTooLong);
+        
+        // Renames that change the normalized handle move the profile to the new handle
+        if ArtistHandle::normalize(&new_name) != ArtistHandle::normalize(&artist_profile.name) {
+            let current_handle_info = context.accounts.current_handle
+                .as_ref()
+                .ok_or(CustomError::HandleAccountMissing)?
+                .to_account_info();
+            let new_handle = context.accounts.new_handle
+                .as_mut()
+                .ok_or(CustomError::HandleAccountMissing)?;
+            
+            new_handle.claim(
+                &new_name,
+                artist_profile.key(),
+                context.bumps.new_handle.unwrap(),
+                clock.unix_timestamp,
+            )?;
+            
+            release_artist_handle(
+                &current_handle_info,
+                artist_profile.key(),
+                &context.accounts.authority.to_account_info(),
+                clock.unix_timestamp,
+            )?;
+        }
+        
         artist_profile.name = new_name;
     }
     
@@ -68,7 +120,189 @@ pub fn update_artist_profile(
     Ok(())
 }
 
+pub(crate) fn propose_artist_profile_transfer(
+    context: Context<UpdateArtistProfileAuthorityAccountConstraints>,
+    new_authority: Pubkey
+) -> Result<()> {
+    let artist_profile = &mut context.accounts.artist_profile;
+    let clock = Clock::get()?;
+    
+    require!(
+        new_authority != Pubkey::default() && new_authority != artist_profile.authority,
+        CustomError::InvalidAuthority
+    );
+    
+    artist_profile.pending_authority = Some(new_authority);
+    artist_profile.updated_at = clock.unix_timestamp;
+    
+    msg!("Artist profile transfer proposed to: {}", new_authority);
+    Ok(())
+}
+
+pub(crate) fn accept_artist_profile_transfer(
+    context: Context<AcceptArtistProfileTransferAccountConstraints>
+) -> Result<()> {
+    let artist_profile = &mut context.accounts.artist_profile;
+    let clock = Clock::get()?;
+    
+    // The profile address is unchanged, so existing master NFTs stay linked to it.
+    // Delegates the previous owner appointed lose their access with the handover.
+    let previous_authority = artist_profile.authority;
+    artist_profile.authority = context.accounts.new_authority.key();
+    artist_profile.pending_authority = None;
+    artist_profile.delegate_generation = artist_profile.delegate_generation.checked_add(1).unwrap();
+    artist_profile.updated_at = clock.unix_timestamp;
+    
+    msg!(
+        "Artist profile {} transferred from {} to {}",
+        artist_profile.name,
+        previous_authority,
+        artist_profile.authority
+    );
+    Ok(())
+}
+
+pub(crate) fn cancel_artist_profile_transfer(
+    context: Context<UpdateArtistProfileAuthorityAccountConstraints>
+) -> Result<()> {
+    let artist_profile = &mut context.accounts.artist_profile;
+    let clock = Clock::get()?;
+    
+    require!(artist_profile.pending_authority.is_some(), CustomError::NoPendingAuthority);
+    
+    artist_profile.pending_authority = None;
+    artist_profile.updated_at = clock.unix_timestamp;
+    
+    msg!("Artist profile transfer cancelled");
+    Ok(())
+}
+
+pub(crate) fn set_artist_delegate(
+    context: Context<SetArtistDelegateAccountConstraints>,
+    permissions: u8
+) -> Result<()> {
+    let artist_delegate = &mut context.accounts.artist_delegate;
+    let clock = Clock::get()?;
+    
+    require!(
+        permissions != 0 && permissions & !DELEGATE_ALL == 0,
+        CustomError::InvalidData
+    );
+    require!(
+        context.accounts.delegate.key() != context.accounts.authority.key(),
+        CustomError::InvalidAuthority
+    );
+    
+    if artist_delegate.artist_profile == Pubkey::default() {
+        artist_delegate.artist_profile = context.accounts.artist_profile.key();
+        artist_delegate.delegate = context.accounts.delegate.key();
+        artist_delegate.granted_at = clock.unix_timestamp;
+        artist_delegate.bump = context.bumps.artist_delegate;
+    }
+    artist_delegate.permissions = permissions;
+    artist_delegate.generation = context.accounts.artist_profile.delegate_generation;
+    artist_delegate.updated_at = clock.unix_timestamp;
+    
+    msg!("Delegate {} granted permissions {:#06b}", artist_delegate.delegate, permissions);
+    Ok(())
+}
+
+pub(crate) fn remove_artist_delegate(
+    context: Context<RemoveArtistDelegateAccountConstraints>
+) -> Result<()> {
+    msg!("Delegate {} removed", context.accounts.artist_delegate.delegate);
+    Ok(())
+}
+
+pub(crate) fn submit_verification_request(
+    context: Context<SubmitVerificationRequestAccountConstraints>,
+    evidence_uri: String,
+    requested_tier: VerificationTier
+) -> Result<()> {
+    require!(
+        evidence_uri.len() <= VerificationRequest::MAX_EVIDENCE_URI_LENGTH,
+        CustomError::StringTooLong
+    );
+    require!(requested_tier != VerificationTier::Unverified, CustomError::InvalidVerificationTier);
+    
+    let verification_request = &mut context.accounts.verification_request;
+    let clock = Clock::get()?;
+    
+    // Resubmitting after a decision reopens the same request and keeps its history
+    if verification_request.artist_profile == Pubkey::default() {
+        verification_request.artist_profile = context.accounts.artist_profile.key();
+        verification_request.history = Vec::new();
+        verification_request.bump = context.bumps.verification_request;
+    } else {
+        require!(
+            verification_request.status != VerificationStatus::Pending,
+            CustomError::VerificationPending
+        );
+    }
+    verification_request.evidence_uri = evidence_uri;
+    verification_request.requested_tier = requested_tier;
+    verification_request.status = VerificationStatus::Pending;
+    verification_request.submitted_at = clock.unix_timestamp;
+    
+    emit!(VerificationRequested {
+        request: verification_request.key(),
+        artist_profile: verification_request.artist_profile,
+        requested_tier,
+        evidence_uri: verification_request.evidence_uri.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Verification requested for artist: {}", context.accounts.artist_profile.name);
+    Ok(())
+}
+
+pub(crate) fn close_artist_profile(context: Context<CloseArtistProfileAccountConstraints>) -> Result<()> {
+    let artist_profile = &context.accounts.artist_profile;
+    let clock = Clock::get()?;
+    
+    // Every master NFT must be closed first so no track points at a missing profile
+    require!(artist_profile.track_count == 0, CustomError::ArtistHasTracks);
+    
+    release_artist_handle(
+        &context.accounts.artist_handle.to_account_info(),
+        artist_profile.key(),
+        &context.accounts.authority.to_account_info(),
+        clock.unix_timestamp,
+    )?;
+    
+    msg!("Artist profile closed for: {}", artist_profile.name);
+    Ok(())
+}
+
+// Reserved handles stay behind for their designated profile; others are closed. Profiles
+// created before the handle registry have no handle to give up.
+pub(crate) fn release_artist_handle<'info>(
+    artist_handle_info: &AccountInfo<'info>,
+    artist_profile: Pubkey,
+    rent_destination: &AccountInfo<'info>,
+    timestamp: i64,
+) -> Result<()> {
+    if artist_handle_info.owner != &crate::ID {
+        return Ok(());
+    }
+    
+    let mut artist_handle = ArtistHandle::try_deserialize(&mut &artist_handle_info.try_borrow_data()?[..])?;
+    if artist_handle.artist_profile != artist_profile {
+        return Ok(());
+    }
+    
+    msg!("Artist handle released: {}", artist_handle.handle);
+    
+    if artist_handle.release(timestamp) {
+        close_program_account(artist_handle_info, rent_destination)
+    } else {
+        artist_handle.try_serialize(&mut *artist_handle_info.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+}
+
 #[derive(Accounts)]
+#[instruction(name: String)]
 pub struct CreateArtistProfileAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -82,21 +316,182 @@ pub struct CreateArtistProfileAccountConstraints<'info> {
     )]
     pub artist_profile: Account<'info, ArtistProfile>,
     
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ArtistHandle::INIT_SPACE,
+        seeds = [ARTIST_HANDLE_SEED, &ArtistHandle::seed_hash(&name)],
+        bump
+    )]
+    pub artist_handle: Account<'info, ArtistHandle>,
+    
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(name: Option<String>)]
 pub struct UpdateArtistProfileAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"artist_profile", authority.key().as_ref()],
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
         bump = artist_profile.bump,
         constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
     )]
     pub artist_profile: Account<'info, ArtistProfile>,
     
+    /// CHECK: Handle for the current name (renames only), released in the handler when the
+    /// profile holds it. Uninitialized for profiles created before the handle registry.
+    #[account(
+        mut,
+        seeds = [ARTIST_HANDLE_SEED, &ArtistHandle::seed_hash(&artist_profile.name)],
+        bump
+    )]
+    pub current_handle: Option<UncheckedAccount<'info>>,
+    
+    // Handle for the new name (renames only)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ArtistHandle::INIT_SPACE,
+        seeds = [ARTIST_HANDLE_SEED, &ArtistHandle::seed_hash(name.as_deref().unwrap_or_default())],
+        bump
+    )]
+    pub new_handle: Option<Account<'info, ArtistHandle>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseArtistProfileAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    /// CHECK: Handle for the profile's name, released in the handler when the profile holds it.
+    /// Uninitialized for profiles created before the handle registry.
+    #[account(
+        mut,
+        seeds = [ARTIST_HANDLE_SEED, &ArtistHandle::seed_hash(&artist_profile.name)],
+        bump
+    )]
+    pub artist_handle: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateArtistProfileAuthorityAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptArtistProfileTransferAccountConstraints<'info> {
+    #[account(mut)]
+    pub new_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.pending_authority == Some(new_authority.key()) @ CustomError::NoPendingAuthority
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetArtistDelegateAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    /// CHECK: Wallet acting on behalf of the artist
+    pub delegate: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ArtistDelegate::INIT_SPACE,
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub artist_delegate: Account<'info, ArtistDelegate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitVerificationRequestAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = VerificationRequest::INIT_SPACE,
+        seeds = [VERIFICATION_REQUEST_SEED, artist_profile.key().as_ref()],
+        bump
+    )]
+    pub verification_request: Account<'info, VerificationRequest>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveArtistDelegateAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), artist_delegate.delegate.as_ref()],
+        bump = artist_delegate.bump
+    )]
+    pub artist_delegate: Account<'info, ArtistDelegate>,
+    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
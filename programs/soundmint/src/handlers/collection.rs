@@ -5,15 +5,27 @@ use crate::state::*;
 use crate::error::CustomError;
 use crate::constants::*;
 
-pub fn create_collection(
+pub(crate) fn create_collection(
     context: Context<CreateCollectionAccountConstraints>,
     name: String,
     description: String,
-    uri: String
+    uri: String,
+    upc: Option<String>
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_COLLECTIONS), CustomError::Paused);
     require!(name.len() <= 100, CustomError::StringTooLong);
     require!(description.len() <= 500, CustomError::StringTooLong);
     require!(uri.len() <= 200, CustomError::StringTooLong);
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_MANAGE_COLLECTIONS
+        ),
+        CustomError::Unauthorized
+    );
     
     let collection = &mut context.accounts.collection;
     let clock = Clock::get()?;
@@ -22,11 +34,35 @@ pub fn create_collection(
     collection.name = name;
     collection.description = description;
     collection.uri = uri;
-    collection.authority = context.accounts.authority.key();
+    collection.artist_profile = context.accounts.artist_profile.key();
     collection.mint = context.accounts.mint.key();
     collection.created_at = clock.unix_timestamp;
     collection.nft_count = 0;
     collection.bump = context.bumps.collection;
+    collection.upc = None;
+    
+    // The registration PDA is keyed by the code, so a UPC can only back one release
+    match (upc, context.accounts.upc_registration.as_mut()) {
+        (Some(code), Some(upc_registration)) => {
+            let code = IdentifierRegistration::canonical_upc(&code);
+            require!(
+                IdentifierRegistration::is_valid_code(IdentifierKind::Upc, &code),
+                CustomError::InvalidIdentifier
+            );
+            
+            upc_registration.register(
+                IdentifierKind::Upc,
+                code.clone(),
+                collection.key(),
+                collection.artist_profile,
+                context.bumps.upc_registration.unwrap(),
+                clock.unix_timestamp,
+            );
+            collection.upc = Some(code);
+        }
+        (None, None) => {}
+        _ => return err!(CustomError::IdentifierAccountMismatch),
+    }
     
     // We would normally add code here to create the NFT metadata for the collection
     // using Metaplex, but we're skipping it since we had issues with metadata integration
@@ -35,17 +71,28 @@ pub fn create_collection(
     Ok(())
 }
 
-pub fn add_to_collection(
+pub(crate) fn add_to_collection(
     context: Context<AddToCollectionAccountConstraints>
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_COLLECTIONS), CustomError::Paused);
+    
     let collection = &mut context.accounts.collection;
     
-    // Verify the caller is the collection authority
+    // Verify the caller is the artist behind the collection or one of their managers
     require!(
-        collection.authority == context.accounts.authority.key(),
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_MANAGE_COLLECTIONS
+        ),
         CustomError::Unauthorized
     );
     
+    // Collections from before profiles owned them are moved over on first use
+    collection.artist_profile = context.accounts.artist_profile.key();
+    
     // Update collection counter
     collection.nft_count = collection.nft_count.checked_add(1).unwrap();
     
@@ -61,11 +108,12 @@ pub struct Collection {
     pub name: String,
     pub description: String,
     pub uri: String,
-    pub authority: Pubkey,
+    pub artist_profile: Pubkey, // Owning profile, whose current wallet and managers act for it
     pub mint: Pubkey,
     pub created_at: i64,
     pub nft_count: u64,
     pub bump: u8,
+    pub upc: Option<String>, // Backed by a unique identifier registration
 }
 
 impl Collection {
@@ -74,6 +122,7 @@ impl Collection {
     pub const U64_LENGTH: usize = 8;
     pub const I64_LENGTH: usize = 8;
     pub const U8_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
     pub const STRING_PREFIX_LENGTH: usize = 4;
     
     pub const INIT_SPACE: usize = 
@@ -81,18 +130,53 @@ impl Collection {
         Self::STRING_PREFIX_LENGTH + 100 +  // name
         Self::STRING_PREFIX_LENGTH + 500 +  // description
         Self::STRING_PREFIX_LENGTH + 200 +  // uri
-        Self::PUBKEY_LENGTH +              // authority
+        Self::PUBKEY_LENGTH +              // artist_profile
         Self::PUBKEY_LENGTH +              // mint
         Self::I64_LENGTH +                 // created_at
         Self::U64_LENGTH +                 // nft_count
-        Self::U8_LENGTH;                   // bump
+        Self::U8_LENGTH +                  // bump
+        Self::OPTION_PREFIX_LENGTH + Self::STRING_PREFIX_LENGTH + IdentifierRegistration::UPC_LENGTH; // upc
+    
+    /// Collections created before they were tied to a profile hold the creating wallet in
+    /// the `artist_profile` slot, which for an artist is the profile's creator
+    pub fn belongs_to(&self, artist_profile: &Account<ArtistProfile>) -> bool {
+        self.artist_profile == artist_profile.key() || self.artist_profile == artist_profile.creator
+    }
 }
 
 #[derive(Accounts)]
+#[instruction(name: String, description: String, uri: String, upc: Option<String>)]
 pub struct CreateCollectionAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, when a label manager is acting on a roster track
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
+    /// CHECK: Artist wallet that receives the collection token
+    #[account(
+        constraint = artist_wallet.key() == artist_profile.authority @ CustomError::Unauthorized
+    )]
+    pub artist_wallet: UncheckedAccount<'info>,
+    
     #[account(
         init,
         payer = authority,
@@ -115,10 +199,32 @@ pub struct CreateCollectionAccountConstraints<'info> {
         init_if_needed,
         payer = authority,
         associated_token::mint = mint,
-        associated_token::authority = authority,
+        associated_token::authority = artist_wallet,
     )]
     pub token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    // Uniqueness record for the release's UPC, when one is supplied
+    #[account(
+        init,
+        payer = authority,
+        space = IdentifierRegistration::INIT_SPACE,
+        seeds = [
+            IDENTIFIER_SEED,
+            &IdentifierKind::Upc.seed(),
+            &IdentifierRegistration::code_seed(
+                &IdentifierRegistration::canonical_upc(upc.as_deref().unwrap_or_default())
+            ),
+        ],
+        bump
+    )]
+    pub upc_registration: Option<Box<Account<'info, IdentifierRegistration>>>,
+    
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -130,19 +236,48 @@ pub struct AddToCollectionAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, when a label manager is acting on a roster track
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
     #[account(
         mut,
         seeds = [SOUND_MINT_COLLECTION_PREFIX, collection.mint.as_ref()],
         bump = collection.bump,
-        constraint = collection.authority == authority.key() @ CustomError::Unauthorized
+        constraint = collection.belongs_to(&artist_profile) @ CustomError::Unauthorized
     )]
     pub collection: Account<'info, Collection>,
     
+    // Only the artist's own tracks can join their collection
     #[account(
         seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
-        bump = master_nft.bump
+        bump = master_nft.bump,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
     )]
     pub master_nft: Account<'info, MasterNft>,
     
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,440 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+use crate::events::{DerivativeAccepted, DerivativeDeclared};
+use crate::handlers::revenue::load_or_create_revenue_epoch;
+
+pub(crate) fn declare_derivative(
+    context: Context<DeclareDerivativeAccountConstraints>,
+    kind: DerivativeKind,
+    upstream_basis_points: u16
+) -> Result<()> {
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_CREATE_SPLITS
+        ),
+        CustomError::Unauthorized
+    );
+    require!(
+        upstream_basis_points > 0 && upstream_basis_points <= MAX_UPSTREAM_BASIS_POINTS,
+        CustomError::UpstreamShareTooHigh
+    );
+    
+    let derivative_link = &mut context.accounts.derivative_link;
+    let clock = Clock::get()?;
+    
+    derivative_link.derivative = context.accounts.master_nft.key();
+    derivative_link.parent = context.accounts.parent_master_nft.key();
+    derivative_link.kind = kind;
+    derivative_link.upstream_basis_points = upstream_basis_points;
+    derivative_link.status = DerivativeLinkStatus::Pending;
+    derivative_link.declared_by = context.accounts.authority.key();
+    derivative_link.declared_at = clock.unix_timestamp;
+    derivative_link.accepted_at = 0;
+    derivative_link.bump = context.bumps.derivative_link;
+    
+    emit!(DerivativeDeclared {
+        link: derivative_link.key(),
+        derivative: derivative_link.derivative,
+        parent: derivative_link.parent,
+        kind,
+        upstream_basis_points,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!(
+        "{} declared as derivative of {}",
+        context.accounts.master_nft.title,
+        context.accounts.parent_master_nft.title
+    );
+    Ok(())
+}
+
+pub(crate) fn accept_derivative(context: Context<AcceptDerivativeAccountConstraints>) -> Result<()> {
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_CREATE_SPLITS
+        ),
+        CustomError::Unauthorized
+    );
+    
+    let derivative_link = &mut context.accounts.derivative_link;
+    let royalty_split = &mut context.accounts.derivative_royalty_split;
+    let clock = Clock::get()?;
+    
+    require!(
+        derivative_link.status == DerivativeLinkStatus::Pending,
+        CustomError::DerivativeLinkNotPending
+    );
+    require!(
+        royalty_split.upstream.len() < RoyaltySplit::MAX_UPSTREAM_SHARES,
+        CustomError::InvalidDerivativeLink
+    );
+    require!(
+        royalty_split.upstream_basis_points()
+            .checked_add(derivative_link.upstream_basis_points).unwrap() <= MAX_UPSTREAM_BASIS_POINTS,
+        CustomError::UpstreamShareTooHigh
+    );
+    
+    // From here on the derivative's revenue cascades into the parent's split
+    royalty_split.upstream.push(UpstreamShare {
+        parent: derivative_link.parent,
+        basis_points: derivative_link.upstream_basis_points,
+        amount_paid: 0,
+    });
+    
+    derivative_link.status = DerivativeLinkStatus::Accepted;
+    derivative_link.accepted_at = clock.unix_timestamp;
+    
+    emit!(DerivativeAccepted {
+        link: derivative_link.key(),
+        derivative: derivative_link.derivative,
+        parent: derivative_link.parent,
+        upstream_basis_points: derivative_link.upstream_basis_points,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Derivative accepted for track: {}", context.accounts.parent_master_nft.title);
+    Ok(())
+}
+
+pub(crate) fn reject_derivative(context: Context<RejectDerivativeAccountConstraints>) -> Result<()> {
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_CREATE_SPLITS
+        ),
+        CustomError::Unauthorized
+    );
+    require!(
+        context.accounts.derivative_link.status == DerivativeLinkStatus::Pending,
+        CustomError::DerivativeLinkNotPending
+    );
+    
+    msg!("Derivative rejected for track: {}", context.accounts.parent_master_nft.title);
+    Ok(())
+}
+
+// What a derivative keeps of its revenue, and each parent's cut to roll up into the parent
+// artist's label
+pub(crate) struct UpstreamRevenue {
+    pub retained: u64,
+    pub label_revenue: Vec<(Pubkey, u64)>,
+}
+
+// Moves each accepted parent's cut of `amount` into that parent's split and revenue epoch,
+// opening the epoch on first use. A cut for a period the parent has already closed is booked
+// into the parent's current period instead. Parents whose split has been closed are skipped
+// and their cut stays with the derivative.
+pub(crate) fn cascade_upstream_revenue<'info>(
+    royalty_split: &mut RoyaltySplit,
+    amount: u64,
+    source: &str,
+    period: u32,
+    remaining_accounts: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<UpstreamRevenue> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut upstream_revenue = UpstreamRevenue {
+        retained: amount,
+        label_revenue: Vec::new(),
+    };
+    
+    for share in royalty_split.upstream.iter_mut() {
+        let parent_split_address = Pubkey::find_program_address(
+            &[ROYALTY_SPLIT_SEED, share.parent.as_ref()],
+            &crate::ID
+        ).0;
+        let parent_split_info = find_upstream_account(remaining_accounts, &parent_split_address)?;
+        if parent_split_info.owner != &crate::ID || parent_split_info.data_is_empty() {
+            continue;
+        }
+        
+        let upstream_amount = amount
+            .checked_mul(share.basis_points as u64).unwrap()
+            .checked_div(TOTAL_BASIS_POINTS as u64).unwrap();
+        if upstream_amount == 0 {
+            continue;
+        }
+        
+        let mut parent_split_data = parent_split_info.try_borrow_mut_data()?;
+        let mut parent_split = RoyaltySplit::try_deserialize(&mut &parent_split_data[..])?;
+        require!(parent_split.revenue_migrated, CustomError::RoyaltySplitNotMigrated);
+        
+        // Late revenue for a period the parent has already closed goes into its current one
+        let current_period = RevenueEpoch::period_for_timestamp(now);
+        let mut parent_epoch_period = if parent_split.accepts_period(period) {
+            period
+        } else {
+            current_period
+        };
+        let mut parent_epoch_info = find_upstream_account(
+            remaining_accounts,
+            &revenue_epoch_address(&share.parent, parent_epoch_period)
+        )?;
+        let mut parent_epoch = load_or_create_revenue_epoch(
+            parent_epoch_info,
+            payer,
+            system_program,
+            &mut parent_split,
+            share.parent,
+            parent_epoch_period,
+            now,
+        )?;
+        
+        if parent_epoch.is_closed {
+            parent_epoch_period = current_period;
+            parent_epoch_info = find_upstream_account(
+                remaining_accounts,
+                &revenue_epoch_address(&share.parent, parent_epoch_period)
+            )?;
+            parent_epoch = load_or_create_revenue_epoch(
+                parent_epoch_info,
+                payer,
+                system_program,
+                &mut parent_split,
+                share.parent,
+                parent_epoch_period,
+                now,
+            )?;
+            require!(!parent_epoch.is_closed, CustomError::EpochClosed);
+        }
+        
+        parent_epoch.record_revenue(upstream_amount, source);
+        parent_epoch.try_serialize(&mut *parent_epoch_info.try_borrow_mut_data()?)?;
+        
+        parent_split.total_revenue_collected = parent_split.total_revenue_collected
+            .checked_add(upstream_amount).unwrap();
+        parent_split.last_revenue_timestamp = now;
+        parent_split.try_serialize(&mut *parent_split_data)?;
+        
+        if let Some(parent_label) = parent_artist_label(remaining_accounts, &share.parent)? {
+            upstream_revenue.label_revenue.push((parent_label, upstream_amount));
+        }
+        
+        share.amount_paid = share.amount_paid.checked_add(upstream_amount).unwrap();
+        upstream_revenue.retained = upstream_revenue.retained.checked_sub(upstream_amount).unwrap();
+    }
+    
+    Ok(upstream_revenue)
+}
+
+// Adds revenue to a label passed among the remaining accounts
+pub(crate) fn record_label_revenue(
+    remaining_accounts: &[AccountInfo],
+    label: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let label_info = remaining_accounts
+        .iter()
+        .find(|account| account.key() == label && account.owner == &crate::ID)
+        .ok_or(CustomError::LabelRequired)?;
+    
+    let mut label_data = label_info.try_borrow_mut_data()?;
+    let mut label = Label::try_deserialize(&mut &label_data[..])?;
+    label.record_revenue(amount);
+    label.try_serialize(&mut *label_data)?;
+    Ok(())
+}
+
+fn find_upstream_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    address: &Pubkey,
+) -> Result<&'a AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account| account.key() == *address)
+        .ok_or(CustomError::UpstreamAccountNotFound.into())
+}
+
+fn revenue_epoch_address(master_nft: &Pubkey, period: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REVENUE_EPOCH_SEED, master_nft.as_ref(), &period.to_le_bytes()],
+        &crate::ID
+    ).0
+}
+
+// The parent master NFT and its artist profile have to be supplied so the parent's label
+// cannot be left out of the rollup
+fn parent_artist_label(remaining_accounts: &[AccountInfo], parent: &Pubkey) -> Result<Option<Pubkey>> {
+    let parent_master_nft_info = find_upstream_account(remaining_accounts, parent)?;
+    require!(parent_master_nft_info.owner == &crate::ID, CustomError::UpstreamAccountNotFound);
+    let parent_master_nft = MasterNft::try_deserialize(&mut &parent_master_nft_info.try_borrow_data()?[..])?;
+    
+    let artist_profile_info = find_upstream_account(remaining_accounts, &parent_master_nft.artist_profile)?;
+    require!(artist_profile_info.owner == &crate::ID, CustomError::UpstreamAccountNotFound);
+    let artist_profile = ArtistProfile::try_deserialize(&mut &artist_profile_info.try_borrow_data()?[..])?;
+    
+    Ok(artist_profile.label)
+}
+
+#[derive(Accounts)]
+pub struct DeclareDerivativeAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, when a label manager is acting for a roster artist
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
+    // The derivative track
+    #[account(
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
+    )]
+    pub master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        seeds = [MASTER_NFT_SEED, parent_master_nft.mint.as_ref()],
+        bump = parent_master_nft.bump,
+        constraint = parent_master_nft.key() != master_nft.key() @ CustomError::InvalidDerivativeLink
+    )]
+    pub parent_master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = DerivativeLink::INIT_SPACE,
+        seeds = [DERIVATIVE_LINK_SEED, master_nft.key().as_ref(), parent_master_nft.key().as_ref()],
+        bump
+    )]
+    pub derivative_link: Account<'info, DerivativeLink>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptDerivativeAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // The parent's artist, who agrees to the upstream share
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, when a label manager is acting for a roster artist
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
+    #[account(
+        seeds = [MASTER_NFT_SEED, parent_master_nft.mint.as_ref()],
+        bump = parent_master_nft.bump,
+        constraint = parent_master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
+    )]
+    pub parent_master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        mut,
+        seeds = [DERIVATIVE_LINK_SEED, derivative_link.derivative.as_ref(), parent_master_nft.key().as_ref()],
+        bump = derivative_link.bump
+    )]
+    pub derivative_link: Account<'info, DerivativeLink>,
+    
+    #[account(
+        mut,
+        seeds = [ROYALTY_SPLIT_SEED, derivative_link.derivative.as_ref()],
+        bump = derivative_royalty_split.bump,
+        constraint = derivative_royalty_split.revenue_migrated @ CustomError::RoyaltySplitNotMigrated
+    )]
+    pub derivative_royalty_split: Box<Account<'info, RoyaltySplit>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectDerivativeAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // The parent's artist, declining the upstream share
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, when a label manager is acting for a roster artist
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
+    #[account(
+        seeds = [MASTER_NFT_SEED, parent_master_nft.mint.as_ref()],
+        bump = parent_master_nft.bump,
+        constraint = parent_master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
+    )]
+    pub parent_master_nft: Box<Account<'info, MasterNft>>,
+    
+    // Rent goes back to whoever declared the link
+    #[account(
+        mut,
+        close = declared_by,
+        seeds = [DERIVATIVE_LINK_SEED, derivative_link.derivative.as_ref(), parent_master_nft.key().as_ref()],
+        bump = derivative_link.bump
+    )]
+    pub derivative_link: Account<'info, DerivativeLink>,
+    
+    /// CHECK: Wallet that paid for the declaration
+    #[account(
+        mut,
+        constraint = declared_by.key() == derivative_link.declared_by @ CustomError::InvalidDerivativeLink
+    )]
+    pub declared_by: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -1,16 +1,30 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 
-pub fn handler(context: Context<Initialize>) -> Result<()> {
+pub(crate) fn handler(context: Context<Initialize>) -> Result<()> {
     let treasury = &mut context.accounts.treasury;
     let clock = Clock::get()?;
     
     treasury.authority = context.accounts.authority.key();
+    treasury.pending_authority = None;
     treasury.treasury_wallet = context.accounts.treasury_wallet.key();
     treasury.streaming_provider = context.accounts.authority.key(); // Set default
+    treasury.guardian = context.accounts.authority.key();
+    treasury.paused_flags = 0;
     treasury.mint_fee = 10_000_000; // 0.01 SOL default fee
     treasury.platform_fee_basis_points = 500; // 5% default platform fee
+    treasury.verified_mint_fee = treasury.mint_fee;
+    treasury.verified_platform_fee_basis_points = treasury.platform_fee_basis_points;
+    treasury.referral_fee_basis_points = 0;
+    treasury.pending_config = None;
     treasury.total_revenue_collected = 0;
+    treasury.total_referral_rewards_paid = 0;
+    treasury.mint_fees = FeeLedger::default();
+    treasury.claim_fees = FeeLedger::default();
+    treasury.streaming_fees = FeeLedger::default();
+    treasury.admins = Vec::new();
+    treasury.admin_threshold = 0;
+    treasury.proposal_count = 0;
     treasury.created_at = clock.unix_timestamp;
     treasury.updated_at = clock.unix_timestamp;
     treasury.bump = context.bumps.treasury;
@@ -5,6 +5,9 @@ pub mod royalty;
 pub mod revenue;
 pub mod streaming;
 pub mod collection;
+pub mod record_label;
+pub mod derivative;
+pub mod program_accounts;
 pub mod admin;
 
 pub use initialize::*;
@@ -14,4 +17,6 @@ pub use royalty::*;
 pub use revenue::*;
 pub use streaming::*;
 pub use collection::*;
+pub use record_label::*;
+pub use derivative::*;
 pub use admin::*;
\ No newline at end of file
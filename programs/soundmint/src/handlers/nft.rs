@@ -3,26 +3,59 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
         create_metadata_accounts_v3,
-        mpl_token_metadata::types::DataV2,
+        mpl_token_metadata::{self, types::{Creator, DataV2}},
+        update_metadata_accounts_v2,
         CreateMetadataAccountsV3,
         Metadata,
+        MetadataAccount,
+        UpdateMetadataAccountsV2,
+    },
+    token::{
+        burn, close_account, freeze_account, mint_to, set_authority, thaw_account,
+        spl_token::instruction::AuthorityType,
+        Burn, CloseAccount, FreezeAccount, Mint, MintTo, SetAuthority, ThawAccount, Token, TokenAccount,
     },
-    token::{Mint, Token, TokenAccount, mint_to, MintTo},
 };
 use crate::state::*;
 use crate::error::CustomError;
+use crate::handlers::program_accounts::close_program_account;
 use crate::constants::*;
+use crate::events::{FeeVoucherRedeemed, MasterNftMinted};
 
-pub fn mint_master_nft(
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mint_master_nft(
     mut context: Context<MintMasterNftAccountConstraints>,
     title: String,
     description: String,
     audio_uri: String,
     artwork_uri: String,
+    audio_hash: [u8; 32],
+    artwork_hash: [u8; 32],
+    audio_spec: AudioSpec,
     metadata: Vec<MetadataItem>,
+    isrc: Option<String>,
+    fingerprint: [u8; 32],
+    version_kind: Option<VersionKind>,
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_MINTING), CustomError::Paused);
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_MINT_TRACKS
+        ),
+        CustomError::Unauthorized
+    );
     validate_inputs(&title, &description, &audio_uri, &artwork_uri, &metadata)?;
-    collect_mint_fee(&mut context)?;
+    validate_content(&audio_hash, &artwork_hash, &audio_spec)?;
+    require!(fingerprint != [0; 32], CustomError::InvalidContentHash);
+    if context.accounts.fee_voucher.is_some() {
+        redeem_fee_voucher(&mut context)?;
+    } else {
+        collect_mint_fee(&mut context)?;
+    }
     update_artist_profile(&mut context.accounts.artist_profile)?;
     initialize_master_nft(
         &mut context.accounts.master_nft,
@@ -33,31 +66,88 @@ pub fn mint_master_nft(
         description,
         audio_uri,
         artwork_uri,
+        audio_hash,
+        artwork_hash,
+        audio_spec,
         metadata, // Added metadata parameter
     )?;
+    register_isrc(&mut context, isrc)?;
+    register_fingerprint(&mut context, fingerprint, version_kind)?;
     mint_nft_token(&context)?;
 
+    // Off-chain metadata generation picks the content fields up from this event
+    emit!(MasterNftMinted {
+        master_nft: context.accounts.master_nft.key(),
+        artist_profile: context.accounts.artist_profile.key(),
+        mint: context.accounts.mint.key(),
+        audio_hash,
+        artwork_hash,
+        duration_seconds: audio_spec.duration_seconds,
+        codec: audio_spec.codec,
+        sample_rate_hz: audio_spec.sample_rate_hz,
+        timestamp: context.accounts.master_nft.created_at,
+    });
+
     msg!("Master NFT minted, metadata creation required");
     Ok(())
 }
 
-pub fn create_nft_metadata(
+pub(crate) fn create_nft_metadata(
     context: Context<CreateMetadataAccountConstraints>,
+    metadata_uri: String,
+    seller_fee_basis_points: u16,
 ) -> Result<()> {
-    create_nft_metadata_account(&context)?;
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_MINT_TRACKS
+        ),
+        CustomError::Unauthorized
+    );
+    require!(
+        MasterNft::is_valid_metadata_uri(&metadata_uri),
+        CustomError::InvalidNftMetadata
+    );
+    require!(
+        seller_fee_basis_points <= TOTAL_BASIS_POINTS,
+        CustomError::InvalidRoyaltyShares
+    );
+
+    create_nft_metadata_account(&context, &metadata_uri, seller_fee_basis_points)?;
+    revoke_mint_authority(&context)?;
+    context.accounts.master_nft.metadata_uri = metadata_uri;
+
     msg!("Metadata created for Master NFT: {}", context.accounts.master_nft.title);
     Ok(())
 }
 
-pub fn update_master_nft(
+pub(crate) fn update_master_nft(
     context: Context<UpdateMasterNftAccountConstraints>,
     description: Option<String>,
     metadata: Option<Vec<MetadataItem>>,
     is_transferable: Option<bool>,
     status: Option<MasterNftStatus>,
+    metadata_uri: Option<String>,
 ) -> Result<()> {
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_UPDATE_METADATA
+        ),
+        CustomError::Unauthorized
+    );
+    
     let master_nft = &mut context.accounts.master_nft;
 
+    // Moderated tracks stay as the moderator left them until the lock is lifted
+    require!(!master_nft.status.is_moderated(), CustomError::TrackUnderModeration);
+
     if let Some(new_description) = description {
         require!(new_description.len() <= 500, CustomError::StringTooLong);
         master_nft.description = new_description;
@@ -82,14 +172,199 @@ pub fn update_master_nft(
     }
 
     if let Some(new_status) = status {
+        require!(
+            master_nft.status.can_artist_transition(new_status),
+            CustomError::InvalidStatusTransition
+        );
         master_nft.status = new_status;
     }
 
-    msg!("Master NFT updated: {}", master_nft.title);
+    // Transfer restrictions are enforced by freezing the holder's token account
+    if is_transferable.is_some() || status.is_some() {
+        let token_account = context.accounts.token_account
+            .as_ref()
+            .ok_or(CustomError::HolderTokenAccountRequired)?;
+
+        set_token_frozen(
+            &context.accounts.token_program,
+            token_account,
+            &context.accounts.mint,
+            &context.accounts.nft_authority,
+            context.bumps.nft_authority,
+            master_nft.is_locked(),
+        )?;
+    }
+
+    // Once token metadata exists, every change has to ship with a new off-chain JSON so
+    // wallets and marketplaces never show a stale record
+    if !master_nft.metadata_uri.is_empty() {
+        let new_metadata_uri = metadata_uri.ok_or(CustomError::MetadataUriRequired)?;
+        require!(
+            MasterNft::is_valid_metadata_uri(&new_metadata_uri),
+            CustomError::InvalidNftMetadata
+        );
+
+        sync_nft_metadata(&context, &new_metadata_uri)?;
+        context.accounts.master_nft.metadata_uri = new_metadata_uri;
+    } else {
+        require!(metadata_uri.is_none(), CustomError::InvalidNftMetadata);
+    }
+
+    msg!("Master NFT updated: {}", context.accounts.master_nft.title);
+    Ok(())
+}
+
+pub(crate) fn close_master_nft(
+    context: Context<CloseMasterNftAccountConstraints>,
+) -> Result<()> {
+    // A track under moderation can't be closed to get around the decision
+    require!(!context.accounts.master_nft.status.is_moderated(), CustomError::TrackUnderModeration);
+    
+    let authority = context.accounts.authority.to_account_info();
+    
+    // Every revenue epoch has to be settled and closed, and every royalty NFT burned, before
+    // the split and tracker can go away
+    let royalty_split_info = context.accounts.royalty_split.to_account_info();
+    if royalty_split_info.owner == &crate::ID {
+        let royalty_split = RoyaltySplit::try_deserialize(&mut &royalty_split_info.try_borrow_data()?[..])?;
+        require!(royalty_split.revenue_migrated, CustomError::RoyaltySplitNotMigrated);
+        require!(royalty_split.revenue_epoch_count == 0, CustomError::UnclaimedRevenue);
+        require!(royalty_split.royalty_nft_count == 0, CustomError::RoyaltyNftsOutstanding);
+        close_program_account(&royalty_split_info, &authority)?;
+    }
+    
+    let revenue_tracker_info = context.accounts.revenue_tracker.to_account_info();
+    if revenue_tracker_info.owner == &crate::ID {
+        close_program_account(&revenue_tracker_info, &authority)?;
+    }
+    
+    // A locked track has to be thawed before its token can be burned
+    if context.accounts.token_account.is_frozen() {
+        set_token_frozen(
+            &context.accounts.token_program,
+            &context.accounts.token_account,
+            &context.accounts.mint,
+            &context.accounts.nft_authority,
+            context.bumps.nft_authority,
+            false,
+        )?;
+    }
+    
+    burn_master_nft_token(&context)?;
+    
+    // Free the ISRC along with the track so it can be registered again
+    require!(
+        context.accounts.master_nft.isrc.is_some() == context.accounts.isrc_registration.is_some(),
+        CustomError::IdentifierAccountMismatch
+    );
+    if let Some(isrc_registration) = &context.accounts.isrc_registration {
+        isrc_registration.close(authority.clone())?;
+    }
+    
+    release_fingerprint(
+        &context.accounts.master_nft,
+        context.accounts.audio_fingerprint.as_mut(),
+        &authority,
+    )?;
+    
+    let artist_profile = &mut context.accounts.artist_profile;
+    artist_profile.track_count = artist_profile.track_count.checked_sub(1).unwrap();
+    
+    msg!("Master NFT closed: {}", context.accounts.master_nft.title);
     Ok(())
 }
 
 // Helper Functions
+fn burn_master_nft_token(context: &Context<CloseMasterNftAccountConstraints>) -> Result<()> {
+    burn(
+        CpiContext::new(
+            context.accounts.token_program.to_account_info(),
+            Burn {
+                mint: context.accounts.mint.to_account_info(),
+                from: context.accounts.token_account.to_account_info(),
+                authority: context.accounts.authority.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    close_account(CpiContext::new(
+        context.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: context.accounts.token_account.to_account_info(),
+            destination: context.accounts.authority.to_account_info(),
+            authority: context.accounts.authority.to_account_info(),
+        },
+    ))?;
+    Ok(())
+}
+
+// Versions give back their slot and the original gives up its claim. The entry is closed
+// by whichever track goes last.
+fn release_fingerprint<'info>(
+    master_nft: &Account<'info, MasterNft>,
+    audio_fingerprint: Option<&mut Box<Account<'info, AudioFingerprint>>>,
+    rent_destination: &AccountInfo<'info>,
+) -> Result<()> {
+    // Tracks minted before fingerprints were required, or whose claim was revoked, have
+    // nothing to release
+    if master_nft.fingerprint == [0; 32] {
+        return Ok(());
+    }
+    let audio_fingerprint = audio_fingerprint.ok_or(CustomError::InvalidData)?;
+    require!(audio_fingerprint.digest == master_nft.fingerprint, CustomError::InvalidData);
+    
+    if audio_fingerprint.original == master_nft.key() {
+        audio_fingerprint.original = Pubkey::default();
+    } else {
+        require!(master_nft.version.is_some(), CustomError::InvalidData);
+        audio_fingerprint.version_count = audio_fingerprint.version_count.checked_sub(1).unwrap();
+    }
+    
+    if audio_fingerprint.is_released() {
+        audio_fingerprint.close(rent_destination.clone())?;
+    }
+    Ok(())
+}
+
+// Freezes or thaws a holder's token account through the program's freeze authority
+pub(crate) fn set_token_frozen<'info>(
+    token_program: &Program<'info, Token>,
+    token_account: &Account<'info, TokenAccount>,
+    mint: &Account<'info, Mint>,
+    nft_authority: &UncheckedAccount<'info>,
+    nft_authority_bump: u8,
+    frozen: bool,
+) -> Result<()> {
+    if token_account.is_frozen() == frozen {
+        return Ok(());
+    }
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[NFT_AUTHORITY_SEED, &[nft_authority_bump]]];
+    if frozen {
+        freeze_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            FreezeAccount {
+                account: token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: nft_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    } else {
+        thaw_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            ThawAccount {
+                account: token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: nft_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+    Ok(())
+}
+
 fn validate_inputs(
     title: &str,
     description: &str,
@@ -111,42 +386,213 @@ fn validate_inputs(
     Ok(())
 }
 
+// The registration PDA is keyed by the code, so a second mint claiming the same ISRC
+// fails when the account is initialized
+fn register_isrc(context: &mut Context<MintMasterNftAccountConstraints>, isrc: Option<String>) -> Result<()> {
+    match (isrc, context.accounts.isrc_registration.as_mut()) {
+        (Some(code), Some(isrc_registration)) => {
+            require!(
+                IdentifierRegistration::is_valid_code(IdentifierKind::Isrc, &code),
+                CustomError::InvalidIdentifier
+            );
+            
+            isrc_registration.register(
+                IdentifierKind::Isrc,
+                code.clone(),
+                context.accounts.master_nft.key(),
+                context.accounts.artist_profile.key(),
+                context.bumps.isrc_registration.unwrap(),
+                context.accounts.master_nft.created_at,
+            );
+            context.accounts.master_nft.isrc = Some(code);
+            Ok(())
+        }
+        (None, None) => Ok(()),
+        _ => err!(CustomError::IdentifierAccountMismatch),
+    }
+}
+
+// A fingerprint can only be registered once. Later mints of the same recording are
+// accepted only as declared versions by the artist who registered it first.
+fn register_fingerprint(
+    context: &mut Context<MintMasterNftAccountConstraints>,
+    fingerprint: [u8; 32],
+    version_kind: Option<VersionKind>,
+) -> Result<()> {
+    let audio_fingerprint = &mut context.accounts.audio_fingerprint;
+    let master_nft = &mut context.accounts.master_nft;
+    
+    if !audio_fingerprint.is_registered() {
+        require!(version_kind.is_none(), CustomError::InvalidVersionLink);
+        require!(
+            audio_fingerprint.can_register_original(&context.accounts.artist_profile.key()),
+            CustomError::FingerprintCollision
+        );
+        
+        // Versions left behind by a closed or revoked original keep counting against the entry
+        audio_fingerprint.digest = fingerprint;
+        audio_fingerprint.original = master_nft.key();
+        audio_fingerprint.artist_profile = context.accounts.artist_profile.key();
+        audio_fingerprint.registered_at = master_nft.created_at;
+        audio_fingerprint.updated_at = master_nft.created_at;
+        audio_fingerprint.bump = context.bumps.audio_fingerprint;
+    } else {
+        let kind = version_kind.ok_or(CustomError::FingerprintCollision)?;
+        require!(
+            audio_fingerprint.artist_profile == context.accounts.artist_profile.key(),
+            CustomError::FingerprintCollision
+        );
+        
+        audio_fingerprint.version_count = audio_fingerprint.version_count.checked_add(1).unwrap();
+        master_nft.version = Some(TrackVersion {
+            original: audio_fingerprint.original,
+            kind,
+        });
+    }
+    
+    master_nft.fingerprint = fingerprint;
+    Ok(())
+}
+
+// Hashes pin the exact files behind the URIs so a swapped upload can be detected
+fn validate_content(
+    audio_hash: &[u8; 32],
+    artwork_hash: &[u8; 32],
+    audio_spec: &AudioSpec,
+) -> Result<()> {
+    require!(*audio_hash != [0; 32], CustomError::InvalidContentHash);
+    require!(*artwork_hash != [0; 32], CustomError::InvalidContentHash);
+    require!(audio_spec.is_valid(), CustomError::InvalidAudioSpec);
+    Ok(())
+}
+
 fn collect_mint_fee(context: &mut Context<MintMasterNftAccountConstraints>) -> Result<()> {
+    let clock = Clock::get()?;
     let treasury = &mut context.accounts.treasury;
-    if treasury.mint_fee > 0 {
+    treasury.apply_pending_config(clock.unix_timestamp);
+    
+    let fee_override = FeeOverride::load(&context.accounts.fee_override)?;
+    let mint_fee = treasury
+        .fee_schedule(
+            context.accounts.artist_profile.is_verified,
+            fee_override.as_ref(),
+            clock.unix_timestamp,
+        )
+        .mint_fee;
+    
+    if mint_fee > 0 {
         require!(
-            **context.accounts.authority.lamports.borrow() > treasury.mint_fee,
+            **context.accounts.authority.lamports.borrow() > mint_fee,
             CustomError::InsufficientFunds
         );
 
+        // Referred artists route part of the fee straight to their referrer
+        let mut referral_reward = match context.accounts.artist_profile.referrer {
+            Some(_) => treasury.referral_reward(mint_fee),
+            None => 0,
+        };
+
+        if referral_reward > 0 {
+            let referrer = context.accounts.referrer
+                .as_ref()
+                .ok_or(CustomError::InvalidReferrer)?;
+
+            // A reward too small to make an unfunded referrer rent exempt would fail the
+            // transfer, so the treasury keeps it rather than blocking the mint
+            let rent_exempt_minimum = Rent::get()?.minimum_balance(referrer.data_len());
+            let referrer_balance = referrer.lamports().saturating_add(referral_reward);
+            if referrer_balance < rent_exempt_minimum {
+                msg!(
+                    "Referral reward of {} lamports kept by the treasury, {} is not rent exempt",
+                    referral_reward,
+                    referrer.key()
+                );
+                referral_reward = 0;
+            } else {
+                let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+                    &context.accounts.authority.key(),
+                    &referrer.key(),
+                    referral_reward,
+                );
+
+                anchor_lang::solana_program::program::invoke(
+                    &transfer_instruction,
+                    &[
+                        context.accounts.authority.to_account_info(),
+                        referrer.to_account_info(),
+                        context.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+
+                treasury.total_referral_rewards_paid = treasury.total_referral_rewards_paid
+                    .checked_add(referral_reward).unwrap();
+                msg!("Referral reward of {} lamports paid to {}", referral_reward, referrer.key());
+            }
+        }
+
+        let treasury_fee = mint_fee.checked_sub(referral_reward).unwrap();
+
+        // Fees accumulate in the treasury PDA until withdrawn by an admin
         let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
             &context.accounts.authority.key(),
-            &treasury.treasury_wallet,
-            treasury.mint_fee,
+            &treasury.key(),
+            treasury_fee,
         );
 
         anchor_lang::solana_program::program::invoke(
             &transfer_instruction,
             &[
                 context.accounts.authority.to_account_info(),
-                context.accounts.treasury_wallet.to_account_info(),
+                treasury.to_account_info(),
                 context.accounts.system_program.to_account_info(),
             ],
         )?;
 
-        treasury.total_revenue_collected = treasury
-            .total_revenue_collected
-            .checked_add(treasury.mint_fee)
-            .unwrap();
+        treasury.record_fee(FeeCategory::Mint, treasury_fee);
     }
     Ok(())
 }
 
+// A voucher waives the mint fee that would otherwise be collected
+fn redeem_fee_voucher(context: &mut Context<MintMasterNftAccountConstraints>) -> Result<()> {
+    let clock = Clock::get()?;
+    let treasury = &mut context.accounts.treasury;
+    treasury.apply_pending_config(clock.unix_timestamp);
+    
+    let fee_override = FeeOverride::load(&context.accounts.fee_override)?;
+    let waived_fee = treasury
+        .fee_schedule(
+            context.accounts.artist_profile.is_verified,
+            fee_override.as_ref(),
+            clock.unix_timestamp,
+        )
+        .mint_fee;
+    
+    let fee_voucher = context.accounts.fee_voucher.as_mut().unwrap();
+    require!(fee_voucher.is_redeemable(clock.unix_timestamp), CustomError::VoucherNotRedeemable);
+    
+    fee_voucher.remaining_uses -= 1;
+    fee_voucher.redeemed_count = fee_voucher.redeemed_count.checked_add(1).unwrap();
+    
+    emit!(FeeVoucherRedeemed {
+        voucher: fee_voucher.key(),
+        beneficiary: fee_voucher.beneficiary,
+        master_nft: context.accounts.master_nft.key(),
+        waived_fee,
+        remaining_uses: fee_voucher.remaining_uses,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Mint fee waived by voucher, {} uses remaining", fee_voucher.remaining_uses);
+    Ok(())
+}
+
 fn update_artist_profile<'info>(artist_profile: &mut Account<'info, ArtistProfile>) -> Result<()> {
     artist_profile.track_count = artist_profile.track_count.checked_add(1).unwrap();
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn initialize_master_nft<'info>(
     master_nft: &mut Account<'info, MasterNft>,
     artist_profile_key: Pubkey,
@@ -156,6 +602,9 @@ fn initialize_master_nft<'info>(
     description: String,
     audio_uri: String,
     artwork_uri: String,
+    audio_hash: [u8; 32],
+    artwork_hash: [u8; 32],
+    audio_spec: AudioSpec,
     metadata: Vec<MetadataItem>,
 ) -> Result<()> {
     let clock = Clock::get()?;
@@ -164,84 +613,171 @@ fn initialize_master_nft<'info>(
     master_nft.artist_profile = artist_profile_key;
     master_nft.audio_uri = audio_uri;
     master_nft.artwork_uri = artwork_uri;
+    master_nft.audio_hash = audio_hash;
+    master_nft.artwork_hash = artwork_hash;
+    master_nft.audio_spec = audio_spec;
+    master_nft.isrc = None;
+    master_nft.fingerprint = [0; 32];
+    master_nft.version = None;
     master_nft.metadata = metadata;
+    master_nft.metadata_uri = String::new();
     master_nft.mint = mint_key;
     master_nft.is_transferable = true;
-    master_nft.status = MasterNftStatus::Active;
+    master_nft.status = MasterNftStatus::Draft;
+    master_nft.moderation_reason = 0;
+    master_nft.moderated_at = 0;
     master_nft.created_at = clock.unix_timestamp;
     master_nft.bump = bump;
     Ok(())
 }
 
+// The program PDA holds mint authority on every fresh master mint and only ever mints
+// this one token; authority is revoked once the metadata program no longer needs it
 fn mint_nft_token(context: &Context<MintMasterNftAccountConstraints>) -> Result<()> {
     msg!("Minting Token");
     mint_to(
-        CpiContext::new(
+        CpiContext::new_with_signer(
             context.accounts.token_program.to_account_info(),
             MintTo {
                 mint: context.accounts.mint.to_account_info(),
                 to: context.accounts.token_account.to_account_info(),
-                authority: context.accounts.authority.to_account_info(),
+                authority: context.accounts.nft_authority.to_account_info(),
             },
+            &[&[NFT_AUTHORITY_SEED, &[context.bumps.nft_authority]]],
         ),
         1,
     )?;
     Ok(())
 }
 
-fn create_nft_uri(
-    title: &str,
-    description: &str,
-    artwork_uri: &str,
-    audio_uri: &str,
-) -> String {
-    format!(
-        "{{\"name\":\"{}\",\"description\":\"{}\",\"image\":\"{}\",\"animation_url\":\"{}\"}}",
-        title, description, artwork_uri, audio_uri
-    )
+// Metaplex needs the mint authority to sign metadata creation, so supply is only locked
+// at 1 after that step. Freeze authority stays with the PDA for transfer restrictions.
+fn revoke_mint_authority(context: &Context<CreateMetadataAccountConstraints>) -> Result<()> {
+    set_authority(
+        CpiContext::new_with_signer(
+            context.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: context.accounts.nft_authority.to_account_info(),
+                account_or_mint: context.accounts.mint.to_account_info(),
+            },
+            &[&[NFT_AUTHORITY_SEED, &[context.bumps.nft_authority]]],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    Ok(())
 }
 
-fn create_nft_metadata_account(context: &Context<CreateMetadataAccountConstraints>) -> Result<()> {
-    msg!("Creating metadata account");
-    let metadata_title = context.accounts.master_nft.title.clone();
-    let metadata_symbol = "SNDM".to_string();
-
-    let nft_uri = create_nft_uri(
-        &metadata_title,
-        &context.accounts.master_nft.description,
-        &context.accounts.master_nft.artwork_uri,
-        &context.accounts.master_nft.audio_uri,
+// Metaplex caps names at 32 bytes; the full title lives in the off-chain JSON
+fn metadata_name(title: &str) -> String {
+    let mut end = title.len().min(mpl_token_metadata::MAX_NAME_LENGTH);
+    while !title.is_char_boundary(end) {
+        end -= 1;
+    }
+    title[..end].to_string()
+}
+
+// Creators mirror the royalty split so marketplaces pay secondary royalties to the same
+// payees. Metaplex shares are whole percentages, so any rounding remainder goes to the
+// largest payee. Payees verify themselves afterwards through the metadata program.
+fn split_creators(royalty_split: &RoyaltySplit) -> Result<Vec<Creator>> {
+    let mut creators: Vec<Creator> = Vec::new();
+    let mut basis_points: Vec<u16> = Vec::new();
+
+    for collaborator in &royalty_split.collaborators {
+        match creators.iter().position(|creator| creator.address == collaborator.address) {
+            Some(index) => {
+                basis_points[index] = basis_points[index]
+                    .checked_add(collaborator.share_basis_points).unwrap();
+            }
+            None => {
+                creators.push(Creator {
+                    address: collaborator.address,
+                    verified: false,
+                    share: 0,
+                });
+                basis_points.push(collaborator.share_basis_points);
+            }
+        }
+    }
+    require!(
+        creators.len() <= mpl_token_metadata::MAX_CREATOR_LIMIT,
+        CustomError::TooManyCreators
     );
 
+    let mut total_share = 0_u8;
+    for (creator, share_basis_points) in creators.iter_mut().zip(&basis_points) {
+        creator.share = (share_basis_points / 100) as u8;
+        total_share = total_share.checked_add(creator.share).unwrap();
+    }
+
+    let largest = (0..creators.len()).max_by_key(|index| basis_points[*index]).unwrap();
+    creators[largest].share = creators[largest].share.checked_add(100 - total_share).unwrap();
+    Ok(creators)
+}
+
+fn sync_nft_metadata(
+    context: &Context<UpdateMasterNftAccountConstraints>,
+    metadata_uri: &str,
+) -> Result<()> {
+    let metadata_account = context.accounts.metadata_account
+        .as_ref()
+        .ok_or(CustomError::InvalidNftMetadata)?;
+    let current = MetadataAccount::try_deserialize(&mut &metadata_account.try_borrow_data()?[..])?;
+
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            context.accounts.token_metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: metadata_account.to_account_info(),
+                update_authority: context.accounts.nft_authority.to_account_info(),
+            },
+            &[&[NFT_AUTHORITY_SEED, &[context.bumps.nft_authority]]],
+        ),
+        None,
+        Some(DataV2 {
+            name: current.name.trim_end_matches('\0').to_string(),
+            symbol: current.symbol.trim_end_matches('\0').to_string(),
+            uri: context.accounts.master_nft.bound_metadata_uri(metadata_uri),
+            seller_fee_basis_points: current.seller_fee_basis_points,
+            creators: current.creators.clone(),
+            collection: current.collection.clone(),
+            uses: current.uses.clone(),
+        }),
+        None,
+        None,
+    )?;
+    Ok(())
+}
+
+fn create_nft_metadata_account(
+    context: &Context<CreateMetadataAccountConstraints>,
+    metadata_uri: &str,
+    seller_fee_basis_points: u16,
+) -> Result<()> {
+    msg!("Creating metadata account");
+    let creators = split_creators(&context.accounts.royalty_split)?;
+
     create_metadata_accounts_v3(
         CpiContext::new_with_signer(
             context.accounts.token_metadata_program.to_account_info(),
             CreateMetadataAccountsV3 {
                 metadata: context.accounts.metadata_account.to_account_info(),
                 mint: context.accounts.mint.to_account_info(),
-                mint_authority: context.accounts.authority.to_account_info(),
+                mint_authority: context.accounts.nft_authority.to_account_info(),
                 payer: context.accounts.authority.to_account_info(),
-                update_authority: context.accounts.authority.to_account_info(),
+                update_authority: context.accounts.nft_authority.to_account_info(),
                 system_program: context.accounts.system_program.to_account_info(),
                 rent: context.accounts.rent.to_account_info(),
             },
-            &[&[
-                b"metadata",
-                context.accounts.token_metadata_program.key().as_ref(),
-                context.accounts.mint.key().as_ref(),
-                &[context.bumps.metadata_account],
-            ]],
+            &[&[NFT_AUTHORITY_SEED, &[context.bumps.nft_authority]]],
         ),
         DataV2 {
-            name: metadata_title,
-            symbol: metadata_symbol,
-            uri: nft_uri,
-            seller_fee_basis_points: 100,
-            creators: Some(vec![anchor_spl::metadata::mpl_token_metadata::types::Creator {
-                address: context.accounts.authority.key(),
-                verified: true,
-                share: 100,
-            }]),
+            name: metadata_name(&context.accounts.master_nft.title),
+            symbol: NFT_SYMBOL.to_string(),
+            uri: context.accounts.master_nft.bound_metadata_uri(metadata_uri),
+            seller_fee_basis_points,
+            creators: Some(creators),
             collection: None,
             uses: None,
         },
@@ -253,18 +789,50 @@ fn create_nft_metadata_account(context: &Context<CreateMetadataAccountConstraint
 }
 
 #[derive(Accounts)]
+#[instruction(
+    title: String,
+    description: String,
+    audio_uri: String,
+    artwork_uri: String,
+    audio_hash: [u8; 32],
+    artwork_hash: [u8; 32],
+    audio_spec: AudioSpec,
+    metadata: Vec<MetadataItem>,
+    isrc: Option<String>,
+    fingerprint: [u8; 32]
+)]
 pub struct MintMasterNftAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [ARTIST_PROFILE_SEED, authority.key().as_ref()],
-        bump,
-        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
     )]
     pub artist_profile: Box<Account<'info, ArtistProfile>>,
 
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+
+    // Artist's label, when a label manager is acting on a roster track
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+
+    /// CHECK: Artist wallet that receives the master NFT token
+    #[account(
+        constraint = artist_wallet.key() == artist_profile.authority @ CustomError::Unauthorized
+    )]
+    pub artist_wallet: UncheckedAccount<'info>,
+
     #[account(
         init,
         payer = authority,
@@ -278,16 +846,23 @@ pub struct MintMasterNftAccountConstraints<'info> {
         init,
         payer = authority,
         mint::decimals = 0,
-        mint::authority = authority.key(),
-        mint::freeze_authority = authority.key(),
+        mint::authority = nft_authority,
+        mint::freeze_authority = nft_authority,
     )]
     pub mint: Box<Account<'info, Mint>>,
 
+    /// CHECK: Program PDA that mints the single master token and holds freeze authority
+    #[account(
+        seeds = [NFT_AUTHORITY_SEED],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = mint,
-        associated_token::authority = authority,
+        associated_token::authority = artist_wallet,
     )]
     pub token_account: Box<Account<'info, TokenAccount>>,
 
@@ -298,12 +873,52 @@ pub struct MintMasterNftAccountConstraints<'info> {
     )]
     pub treasury: Box<Account<'info, Treasury>>,
 
-    /// CHECK: Treasury wallet account
+    /// CHECK: Per-artist fee override address, uninitialized when the artist has no override.
+    /// Required so a minter cannot leave out an override that raises their fee.
+    #[account(
+        seeds = [FEE_OVERRIDE_SEED, artist_profile.key().as_ref()],
+        bump
+    )]
+    pub fee_override: UncheckedAccount<'info>,
+
+    // Fee waiver voucher issued to this artist, redeemed in place of the mint fee
+    #[account(
+        mut,
+        seeds = [FEE_VOUCHER_SEED, artist_profile.key().as_ref()],
+        bump = fee_voucher.bump
+    )]
+    pub fee_voucher: Option<Box<Account<'info, FeeVoucher>>>,
+
+    /// CHECK: Referrer recorded on the artist profile, required when a referral reward is due
     #[account(
         mut,
-        constraint = treasury_wallet.key() == treasury.treasury_wallet @ CustomError::Unauthorized
+        constraint = artist_profile.referrer == Some(referrer.key()) @ CustomError::InvalidReferrer
     )]
-    pub treasury_wallet: UncheckedAccount<'info>,
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    // Uniqueness record for the track's ISRC, when one is supplied
+    #[account(
+        init,
+        payer = authority,
+        space = IdentifierRegistration::INIT_SPACE,
+        seeds = [
+            IDENTIFIER_SEED,
+            &IdentifierKind::Isrc.seed(),
+            &IdentifierRegistration::code_seed(isrc.as_deref().unwrap_or_default()),
+        ],
+        bump
+    )]
+    pub isrc_registration: Option<Box<Account<'info, IdentifierRegistration>>>,
+
+    // Registry entry for the recording's perceptual fingerprint
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AudioFingerprint::INIT_SPACE,
+        seeds = [AUDIO_FINGERPRINT_SEED, fingerprint.as_ref()],
+        bump
+    )]
+    pub audio_fingerprint: Box<Account<'info, AudioFingerprint>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -316,14 +931,43 @@ pub struct CreateMetadataAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+
+    // Artist's label, when a label manager is acting on a roster track
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+
     #[account(
         mut,
         seeds = [MASTER_NFT_SEED, mint.key().as_ref()],
         bump = master_nft.bump,
-        constraint = master_nft.mint == mint.key() @ CustomError::Unauthorized
+        constraint = master_nft.mint == mint.key() @ CustomError::Unauthorized,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
     )]
     pub master_nft: Box<Account<'info, MasterNft>>,
 
+    // Creators are taken from the split, so it has to exist before metadata is written
+    #[account(
+        seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
+        bump = royalty_split.bump
+    )]
+    pub royalty_split: Box<Account<'info, RoyaltySplit>>,
+
     #[account(mut)]
     pub mint: Box<Account<'info, Mint>>,
 
@@ -335,11 +979,20 @@ pub struct CreateMetadataAccountConstraints<'info> {
             token_metadata_program.key().as_ref(),
             mint.key().as_ref(),
         ],
+        seeds::program = token_metadata_program.key(),
         bump
     )]
     pub metadata_account: UncheckedAccount<'info>,
 
+    /// CHECK: Program PDA that owns the update authority on every master NFT's metadata
+    #[account(
+        seeds = [NFT_AUTHORITY_SEED],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+
     pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -351,19 +1004,225 @@ pub struct UpdateMasterNftAccountConstraints<'info> {
 
     #[account(
         mut,
-        seeds = [ARTIST_PROFILE_SEED, authority.key().as_ref()],
-        bump,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+
+    // Artist's label, when a label manager is acting on a roster track
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+
+    #[account(
+        mut,
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
+    )]
+    pub master_nft: Box<Account<'info, MasterNft>>,
+
+    /// CHECK: Token metadata for the track, required once it has been created
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            master_nft.mint.as_ref(),
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        constraint = master_nft.mint == mint.key() @ CustomError::InvalidData
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    // Current holder's token account, required when transferability or status changes
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ CustomError::InvalidData,
+        constraint = token_account.amount == 1 @ CustomError::InvalidData
+    )]
+    pub token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Program PDA holding metadata update authority and the mint's freeze authority
+    #[account(
+        seeds = [NFT_AUTHORITY_SEED],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseMasterNftAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
         constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
     )]
     pub artist_profile: Box<Account<'info, ArtistProfile>>,
 
     #[account(
         mut,
+        close = authority,
         seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
         bump = master_nft.bump,
         constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
     )]
     pub master_nft: Box<Account<'info, MasterNft>>,
 
+    #[account(
+        mut,
+        constraint = master_nft.mint == mint.key() @ CustomError::InvalidData
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Royalty split PDA for the track, closed if it was ever created
+    #[account(
+        mut,
+        seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
+        bump
+    )]
+    pub royalty_split: UncheckedAccount<'info>,
+
+    /// CHECK: Revenue tracker PDA for the track, closed if it was ever created
+    #[account(
+        mut,
+        seeds = [REVENUE_TRACKER_SEED, master_nft.key().as_ref()],
+        bump
+    )]
+    pub revenue_tracker: UncheckedAccount<'info>,
+
+    // ISRC registration held by the track, required when it has one
+    #[account(
+        mut,
+        seeds = [
+            IDENTIFIER_SEED,
+            &IdentifierKind::Isrc.seed(),
+            &IdentifierRegistration::code_seed(&isrc_registration.code),
+        ],
+        bump = isrc_registration.bump,
+        constraint = isrc_registration.holder == master_nft.key() @ CustomError::IdentifierAccountMismatch
+    )]
+    pub isrc_registration: Option<Box<Account<'info, IdentifierRegistration>>>,
+
+    // Fingerprint registry entry for the track's recording
+    #[account(
+        mut,
+        seeds = [AUDIO_FINGERPRINT_SEED, audio_fingerprint.digest.as_ref()],
+        bump = audio_fingerprint.bump
+    )]
+    pub audio_fingerprint: Option<Box<Account<'info, AudioFingerprint>>>,
+
+    /// CHECK: Program PDA holding the mint's freeze authority
+    #[account(
+        seeds = [NFT_AUTHORITY_SEED],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn royalty_split(shares: &[(Pubkey, u16)]) -> RoyaltySplit {
+        RoyaltySplit {
+            master_nft: Pubkey::new_unique(),
+            collaborators: shares
+                .iter()
+                .map(|(address, share_basis_points)| Collaborator {
+                    address: *address,
+                    name: String::new(),
+                    share_basis_points: *share_basis_points,
+                    amount_claimed: 0,
+                })
+                .collect(),
+            total_basis_points: TOTAL_BASIS_POINTS,
+            total_revenue_collected: 0,
+            created_at: 0,
+            last_revenue_timestamp: 0,
+            bump: 0,
+            revenue_migrated: true,
+            revenue_epoch_count: 0,
+            royalty_nft_count: 0,
+            minted_basis_points: 0,
+            upstream: Vec::new(),
+            closed_through_period: 0,
+            minted_shares: Vec::new(),
+        }
+    }
+
+    fn shares(creators: &[Creator]) -> Vec<(Pubkey, u8)> {
+        creators.iter().map(|creator| (creator.address, creator.share)).collect()
+    }
+
+    #[test]
+    fn split_creators_converts_basis_points_to_percentages() {
+        let (artist, producer, featured) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let creators = split_creators(&royalty_split(&[(artist, 7000), (producer, 2000), (featured, 1000)])).unwrap();
+
+        assert_eq!(shares(&creators), vec![(artist, 70), (producer, 20), (featured, 10)]);
+        assert!(creators.iter().all(|creator| !creator.verified));
+    }
+
+    #[test]
+    fn split_creators_merges_repeated_payees() {
+        let (artist, producer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let creators = split_creators(&royalty_split(&[(artist, 5000), (producer, 2500), (artist, 2500)])).unwrap();
+
+        assert_eq!(shares(&creators), vec![(artist, 75), (producer, 25)]);
+    }
+
+    #[test]
+    fn split_creators_gives_rounding_remainder_to_largest_payee() {
+        let (first, second, largest) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let creators = split_creators(&royalty_split(&[(first, 3333), (second, 3333), (largest, 3334)])).unwrap();
+
+        assert_eq!(shares(&creators), vec![(first, 33), (second, 33), (largest, 34)]);
+        assert_eq!(creators.iter().map(|creator| creator.share as u16).sum::<u16>(), 100);
+    }
+
+    #[test]
+    fn split_creators_rejects_more_payees_than_metaplex_allows() {
+        let payees: Vec<(Pubkey, u16)> = (0..6)
+            .map(|index| (Pubkey::new_unique(), if index == 0 { 5000 } else { 1000 }))
+            .collect();
+
+        assert_eq!(
+            split_creators(&royalty_split(&payees)).unwrap_err(),
+            CustomError::TooManyCreators.into()
+        );
+    }
+}
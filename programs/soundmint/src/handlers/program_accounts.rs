@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+
+// Used for PDAs that may not exist yet, which cannot be declared as `init` accounts.
+// `signer_seeds` must include the bump; the caller serializes the account data afterwards.
+pub(crate) fn create_program_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    
+    if current_lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    
+    // Anyone can send lamports to an address before it is created, which makes
+    // `create_account` fail. Top up the rent and allocate and assign instead.
+    let top_up = rent.saturating_sub(current_lamports);
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+// Used for PDAs that may not exist, which cannot be declared as typed accounts
+pub(crate) fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    rent_destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **rent_destination.try_borrow_mut_lamports()? = rent_destination.lamports().checked_add(lamports).unwrap();
+    **account.try_borrow_mut_lamports()? = 0;
+    
+    account.assign(&System::id());
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+// Grows an account the program already owns, with the payer covering the extra rent
+pub(crate) fn resize_program_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(space, true)?;
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::constants::*;
+
+pub(crate) fn create_label(
+    context: Context<CreateLabelAccountConstraints>,
+    name: String,
+    royalty_wallet: Pubkey,
+    default_share_basis_points: u16
+) -> Result<()> {
+    require!(name.len() <= Label::MAX_NAME_LENGTH, CustomError::StringTooLong);
+    require!(
+        default_share_basis_points < TOTAL_BASIS_POINTS,
+        CustomError::InvalidRoyaltyShares
+    );
+    
+    let label = &mut context.accounts.label;
+    let clock = Clock::get()?;
+    
+    label.authority = context.accounts.authority.key();
+    label.name = name;
+    label.royalty_wallet = royalty_wallet;
+    label.default_share_basis_points = default_share_basis_points;
+    label.managers = Vec::new();
+    label.artist_count = 0;
+    label.total_revenue = 0;
+    label.created_at = clock.unix_timestamp;
+    label.updated_at = clock.unix_timestamp;
+    label.bump = context.bumps.label;
+    
+    msg!("Label created: {}", label.name);
+    Ok(())
+}
+
+pub(crate) fn update_label(
+    context: Context<UpdateLabelAccountConstraints>,
+    name: Option<String>,
+    royalty_wallet: Option<Pubkey>,
+    default_share_basis_points: Option<u16>,
+    managers: Option<Vec<Pubkey>>
+) -> Result<()> {
+    let label = &mut context.accounts.label;
+    let clock = Clock::get()?;
+    
+    if let Some(new_name) = name {
+        require!(new_name.len() <= Label::MAX_NAME_LENGTH, CustomError::StringTooLong);
+        label.name = new_name;
+    }
+    
+    if let Some(new_royalty_wallet) = royalty_wallet {
+        label.royalty_wallet = new_royalty_wallet;
+    }
+    
+    // Only affects splits created from now on
+    if let Some(new_share) = default_share_basis_points {
+        require!(new_share < TOTAL_BASIS_POINTS, CustomError::InvalidRoyaltyShares);
+        label.default_share_basis_points = new_share;
+    }
+    
+    if let Some(new_managers) = managers {
+        require!(new_managers.len() <= Label::MAX_MANAGERS, CustomError::TooManyManagers);
+        label.managers = new_managers;
+    }
+    
+    label.updated_at = clock.unix_timestamp;
+    
+    msg!("Label updated: {}", label.name);
+    Ok(())
+}
+
+pub(crate) fn invite_artist_to_label(
+    context: Context<InviteArtistToLabelAccountConstraints>
+) -> Result<()> {
+    let label = &context.accounts.label;
+    let membership = &mut context.accounts.membership;
+    let clock = Clock::get()?;
+    
+    require!(label.is_manager(&context.accounts.authority.key()), CustomError::Unauthorized);
+    require!(context.accounts.artist_profile.label.is_none(), CustomError::AlreadyInLabel);
+    
+    membership.label = label.key();
+    membership.artist_profile = context.accounts.artist_profile.key();
+    membership.status = MembershipStatus::Invited;
+    membership.invited_by = context.accounts.authority.key();
+    membership.invited_at = clock.unix_timestamp;
+    membership.joined_at = 0;
+    membership.bump = context.bumps.membership;
+    
+    msg!("Label {} invited artist: {}", label.name, context.accounts.artist_profile.name);
+    Ok(())
+}
+
+pub(crate) fn accept_label_invite(
+    context: Context<AcceptLabelInviteAccountConstraints>
+) -> Result<()> {
+    let label = &mut context.accounts.label;
+    let membership = &mut context.accounts.membership;
+    let artist_profile = &mut context.accounts.artist_profile;
+    let clock = Clock::get()?;
+    
+    // Joining needs the artist's own signature, so labels can never claim an artist unilaterally
+    require!(membership.status == MembershipStatus::Invited, CustomError::AlreadyLabelMember);
+    require!(artist_profile.label.is_none(), CustomError::AlreadyInLabel);
+    
+    membership.status = MembershipStatus::Active;
+    membership.joined_at = clock.unix_timestamp;
+    artist_profile.label = Some(label.key());
+    artist_profile.updated_at = clock.unix_timestamp;
+    label.artist_count = label.artist_count.checked_add(1).unwrap();
+    
+    msg!("Artist {} joined label: {}", artist_profile.name, label.name);
+    Ok(())
+}
+
+pub(crate) fn end_label_membership(
+    context: Context<EndLabelMembershipAccountConstraints>
+) -> Result<()> {
+    let label = &mut context.accounts.label;
+    let membership = &context.accounts.membership;
+    let artist_profile = &mut context.accounts.artist_profile;
+    let signer = context.accounts.authority.key();
+    let clock = Clock::get()?;
+    
+    // Either side can walk away or withdraw a pending invite
+    require!(
+        artist_profile.authority == signer || label.is_manager(&signer),
+        CustomError::Unauthorized
+    );
+    
+    if membership.status == MembershipStatus::Active {
+        artist_profile.label = None;
+        artist_profile.updated_at = clock.unix_timestamp;
+        label.artist_count = label.artist_count.checked_sub(1).unwrap();
+    }
+    
+    msg!("Artist {} left label: {}", artist_profile.name, label.name);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateLabelAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Label::INIT_SPACE,
+        seeds = [LABEL_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub label: Account<'info, Label>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLabelAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LABEL_SEED, authority.key().as_ref()],
+        bump = label.bump,
+        constraint = label.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub label: Account<'info, Label>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InviteArtistToLabelAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump
+    )]
+    pub label: Account<'info, Label>,
+    
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = LabelMembership::INIT_SPACE,
+        seeds = [LABEL_MEMBERSHIP_SEED, label.key().as_ref(), artist_profile.key().as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, LabelMembership>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptLabelInviteAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump
+    )]
+    pub label: Account<'info, Label>,
+    
+    #[account(
+        mut,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        mut,
+        seeds = [LABEL_MEMBERSHIP_SEED, label.key().as_ref(), artist_profile.key().as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, LabelMembership>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EndLabelMembershipAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump
+    )]
+    pub label: Account<'info, Label>,
+    
+    #[account(
+        mut,
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Account<'info, ArtistProfile>,
+    
+    #[account(
+        mut,
+        close = invited_by,
+        seeds = [LABEL_MEMBERSHIP_SEED, label.key().as_ref(), artist_profile.key().as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, LabelMembership>,
+    
+    /// CHECK: Wallet that paid for the invite and gets the rent back
+    #[account(
+        mut,
+        constraint = invited_by.key() == membership.invited_by @ CustomError::InvalidData
+    )]
+    pub invited_by: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -2,20 +2,47 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::CustomError;
 use crate::constants::*;
+use crate::handlers::derivative::{cascade_upstream_revenue, record_label_revenue};
+use crate::handlers::program_accounts::{close_program_account, create_program_account};
 
-pub fn track_revenue(
-    context: Context<TrackRevenueAccountConstraints>,
+pub(crate) fn track_revenue<'info>(
+    context: Context<'_, '_, '_, 'info, TrackRevenueAccountConstraints<'info>>,
     amount: u64,
     source: String,
-    description: String
+    description: String,
+    period: u32
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_REVENUE_TRACKING), CustomError::Paused);
     require!(amount > 0, CustomError::InvalidAmount);
     require!(source.len() <= 20, CustomError::StringTooLong);
     require!(description.len() <= 100, CustomError::StringTooLong);
+    require!(context.accounts.master_nft.status.accepts_revenue(), CustomError::TrackNotEarning);
     
     let clock = Clock::get()?;
     let revenue_tracker = &mut context.accounts.revenue_tracker;
     let royalty_split = &mut context.accounts.royalty_split;
+    let revenue_epoch = &mut context.accounts.revenue_epoch;
+    
+    // Revenue can be booked into the current or an earlier period until it is closed
+    require!(
+        RevenueEpoch::is_valid_period(period)
+            && period <= RevenueEpoch::period_for_timestamp(clock.unix_timestamp),
+        CustomError::InvalidEpochPeriod
+    );
+    require!(royalty_split.accepts_period(period), CustomError::EpochClosed);
+    
+    if revenue_epoch.master_nft == Pubkey::default() {
+        initialize_revenue_epoch(
+            revenue_epoch,
+            context.accounts.master_nft.key(),
+            period,
+            context.bumps.revenue_epoch,
+            context.accounts.authority.key(),
+            clock.unix_timestamp,
+        );
+        royalty_split.revenue_epoch_count = royalty_split.revenue_epoch_count.checked_add(1).unwrap();
+    }
+    require!(!revenue_epoch.is_closed, CustomError::EpochClosed);
     
     // Create a new transaction record
     let transaction = RevenueTransaction {
@@ -40,53 +67,135 @@ pub fn track_revenue(
     revenue_tracker.transactions.push(transaction);
     revenue_tracker.last_revenue_timestamp = clock.unix_timestamp;
     
+    // Parents of a derivative take their agreed cut before the track's own holders
+    let upstream_revenue = cascade_upstream_revenue(
+        royalty_split,
+        amount,
+        &source,
+        period,
+        context.remaining_accounts,
+        &context.accounts.authority.to_account_info(),
+        &context.accounts.system_program.to_account_info(),
+    )?;
+    let retained = upstream_revenue.retained;
+    
     // Update royalty split total revenue
-    royalty_split.total_revenue_collected = royalty_split.total_revenue_collected.checked_add(amount).unwrap();
+    royalty_split.total_revenue_collected = royalty_split.total_revenue_collected.checked_add(retained).unwrap();
     royalty_split.last_revenue_timestamp = clock.unix_timestamp;
     
-    msg!("Revenue tracked: {} lamports from {}", amount, source);
+    // Book the revenue into its settlement epoch
+    revenue_epoch.record_revenue(retained, &source);
+    
+    // Roll roster revenue up into the artist's label, and each parent's cut into theirs
+    if context.accounts.artist_profile.label.is_some() {
+        let label = context.accounts.label
+            .as_mut()
+            .ok_or(CustomError::LabelRequired)?;
+        label.record_revenue(retained);
+    }
+    for (parent_label, upstream_amount) in upstream_revenue.label_revenue {
+        // The artist's own label is written back on exit, so it cannot be updated in place
+        match context.accounts.label.as_mut() {
+            Some(label) if label.key() == parent_label => label.record_revenue(upstream_amount),
+            _ => record_label_revenue(context.remaining_accounts, parent_label, upstream_amount)?,
+        }
+    }
+    
+    msg!("Revenue tracked: {} lamports from {} for period {}", amount, source, period);
     Ok(())
 }
 
-pub fn claim_revenue(
+pub(crate) fn open_revenue_epoch(
+    context: Context<OpenRevenueEpochAccountConstraints>,
+    period: u32
+) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_REVENUE_TRACKING), CustomError::Paused);
+    
+    let clock = Clock::get()?;
+    
+    require!(
+        RevenueEpoch::is_valid_period(period)
+            && period <= RevenueEpoch::period_for_timestamp(clock.unix_timestamp),
+        CustomError::InvalidEpochPeriod
+    );
+    require!(context.accounts.royalty_split.accepts_period(period), CustomError::EpochClosed);
+    
+    initialize_revenue_epoch(
+        &mut context.accounts.revenue_epoch,
+        context.accounts.master_nft.key(),
+        period,
+        context.bumps.revenue_epoch,
+        context.accounts.payer.key(),
+        clock.unix_timestamp,
+    );
+    
+    let royalty_split = &mut context.accounts.royalty_split;
+    royalty_split.revenue_epoch_count = royalty_split.revenue_epoch_count.checked_add(1).unwrap();
+    
+    msg!("Revenue epoch {} opened for track: {}", period, context.accounts.master_nft.title);
+    Ok(())
+}
+
+pub(crate) fn claim_revenue(
     context: Context<ClaimRevenueAccountConstraints>
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_CLAIMS), CustomError::Paused);
+    require!(context.accounts.master_nft.status.allows_claims(), CustomError::ClaimsSuspended);
+    
     let clock = Clock::get()?;
     let royalty_nft = &mut context.accounts.royalty_nft;
-    let royalty_split = &context.accounts.royalty_split;
-    let treasury = &context.accounts.treasury;
+    let revenue_epoch = &mut context.accounts.revenue_epoch;
+    let royalty_statement = &mut context.accounts.royalty_statement;
+    let treasury = &mut context.accounts.treasury;
+    treasury.apply_pending_config(clock.unix_timestamp);
+    
+    // Only frozen periods can be settled so statements never change after the fact
+    require!(revenue_epoch.is_closed, CustomError::EpochNotClosed);
     
     // Calculate claimable amount
-    let total_revenue = royalty_split.total_revenue_collected;
+    let epoch_revenue = revenue_epoch.total_revenue;
     let share_percentage = royalty_nft.share_basis_points as u64;
-    let platform_fee_percentage = treasury.platform_fee_basis_points as u64;
+    let fee_override = FeeOverride::load(&context.accounts.fee_override)?;
+    let platform_fee_percentage = treasury
+        .fee_schedule(
+            context.accounts.artist_profile.is_verified,
+            fee_override.as_ref(),
+            clock.unix_timestamp,
+        )
+        .platform_fee_basis_points as u64;
     
-    // Calculate revenue share (total * share_percentage / TOTAL_BASIS_POINTS)
-    let gross_share = total_revenue
+    // Calculate revenue share (epoch revenue * share_percentage / TOTAL_BASIS_POINTS)
+    let entitled_amount = epoch_revenue
         .checked_mul(share_percentage).unwrap()
         .checked_div(TOTAL_BASIS_POINTS as u64).unwrap();
     
-    // Calculate amount already claimed
-    let already_claimed = royalty_nft.amount_claimed;
+    // Revenue collected before epochs may already have been partly paid out
+    let is_legacy_epoch = revenue_epoch.period == LEGACY_REVENUE_PERIOD;
+    let claimable_amount = if is_legacy_epoch {
+        entitled_amount.saturating_sub(royalty_nft.amount_claimed)
+    } else {
+        entitled_amount
+    };
     
-    // Calculate net claimable amount
-    let claimable_amount = gross_share.checked_sub(already_claimed).unwrap();
-    require!(claimable_amount > 0, CustomError::NoRevenueToClaim);
+    // The epoch can never pay out more than was recorded in it
+    let total_claimed = revenue_epoch.total_claimed.checked_add(claimable_amount).unwrap();
+    require!(total_claimed <= epoch_revenue, CustomError::EpochOverClaimed);
     
     // Calculate platform fee
     let platform_fee = claimable_amount
         .checked_mul(platform_fee_percentage).unwrap()
         .checked_div(TOTAL_BASIS_POINTS as u64).unwrap();
     
-    // Final amount after platform fee
+    // Final amount after platform fee. A share that rounds down to nothing still records
+    // its statement so the epoch can be settled and closed.
     let final_amount = claimable_amount.checked_sub(platform_fee).unwrap();
-    require!(final_amount > 0, CustomError::AmountTooSmall);
+    require!(claimable_amount == 0 || final_amount > 0, CustomError::AmountTooSmall);
     
     // Transfer platform fee to treasury
     if platform_fee > 0 {
         let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
             &context.accounts.payer.key(),
-            &treasury.treasury_wallet,
+            &treasury.key(),
             platform_fee
         );
         
@@ -94,37 +203,180 @@ pub fn claim_revenue(
             &transfer_instruction,
             &[
                 context.accounts.payer.to_account_info(),
-                context.accounts.treasury_wallet.to_account_info(),
+                treasury.to_account_info(),
                 context.accounts.system_program.to_account_info(),
             ]
         )?;
+        
+        treasury.record_fee(FeeCategory::Claims, platform_fee);
     }
     
     // Transfer funds to the royalty NFT holder
-    let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
-        &context.accounts.payer.key(),
-        &context.accounts.authority.key(),
-        final_amount
+    if final_amount > 0 {
+        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+            &context.accounts.payer.key(),
+            &context.accounts.authority.key(),
+            final_amount
+        );
+        
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                context.accounts.payer.to_account_info(),
+                context.accounts.authority.to_account_info(),
+                context.accounts.system_program.to_account_info(),
+            ]
+        )?;
+    }
+    
+    // Update royalty NFT and epoch state
+    if is_legacy_epoch {
+        royalty_nft.amount_claimed = royalty_nft.amount_claimed.checked_add(claimable_amount).unwrap();
+    }
+    royalty_nft.last_claimed_at = clock.unix_timestamp;
+    revenue_epoch.total_claimed = total_claimed;
+    revenue_epoch.claimed_basis_points = revenue_epoch.claimed_basis_points
+        .checked_add(royalty_nft.share_basis_points).unwrap();
+    revenue_epoch.statement_count = revenue_epoch.statement_count.checked_add(1).unwrap();
+    
+    // Record the statement line for this royalty NFT and period
+    royalty_statement.revenue_epoch = revenue_epoch.key();
+    royalty_statement.royalty_nft = royalty_nft.key();
+    royalty_statement.period = revenue_epoch.period;
+    royalty_statement.share_basis_points = royalty_nft.share_basis_points;
+    royalty_statement.gross_amount = claimable_amount;
+    royalty_statement.platform_fee = platform_fee;
+    royalty_statement.net_amount = final_amount;
+    royalty_statement.claimed_at = clock.unix_timestamp;
+    royalty_statement.bump = context.bumps.royalty_statement;
+    royalty_statement.claimant = context.accounts.authority.key();
+    
+    msg!("Revenue claimed for period {}: {} lamports", revenue_epoch.period, final_amount);
+    Ok(())
+}
+
+// A settled epoch is closed along with every statement recorded against it, and the rent
+// goes back to whoever paid for each account. Statements are passed as remaining accounts,
+// each followed by its claimant.
+pub(crate) fn close_revenue_epoch<'info>(
+    context: Context<'_, '_, '_, 'info, CloseRevenueEpochAccountConstraints<'info>>
+) -> Result<()> {
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_CREATE_SPLITS
+        ),
+        CustomError::Unauthorized
     );
     
-    anchor_lang::solana_program::program::invoke(
-        &transfer_instruction,
-        &[
-            context.accounts.payer.to_account_info(),
-            context.accounts.authority.to_account_info(),
-            context.accounts.system_program.to_account_info(),
-        ]
-    )?;
+    let revenue_epoch = &context.accounts.revenue_epoch;
+    let royalty_split = &mut context.accounts.royalty_split;
     
-    // Update royalty NFT state
-    royalty_nft.amount_claimed = royalty_nft.amount_claimed.checked_add(claimable_amount).unwrap();
-    royalty_nft.last_claimed_at = clock.unix_timestamp;
+    require!(
+        revenue_epoch.is_settled(royalty_split.minted_basis_points),
+        CustomError::EpochNotSettled
+    );
+    require!(
+        context.remaining_accounts.len() == revenue_epoch.statement_count as usize * 2,
+        CustomError::RoyaltyStatementsMissing
+    );
     
-    msg!("Revenue claimed: {} lamports", final_amount);
+    // A statement closed once is no longer program owned, so none can be counted twice
+    for statement_accounts in context.remaining_accounts.chunks(2) {
+        let (royalty_statement_info, claimant_info) = (&statement_accounts[0], &statement_accounts[1]);
+        require!(royalty_statement_info.owner == &crate::ID, CustomError::RoyaltyStatementsMissing);
+        
+        let royalty_statement = RoyaltyStatement::try_deserialize(
+            &mut &royalty_statement_info.try_borrow_data()?[..]
+        )?;
+        require!(
+            royalty_statement.revenue_epoch == revenue_epoch.key(),
+            CustomError::RoyaltyStatementsMissing
+        );
+        require!(royalty_statement.claimant == claimant_info.key(), CustomError::InvalidData);
+        
+        close_program_account(royalty_statement_info, claimant_info)?;
+    }
+    
+    royalty_split.revenue_epoch_count = royalty_split.revenue_epoch_count.checked_sub(1).unwrap();
+    royalty_split.closed_through_period = royalty_split.closed_through_period.max(revenue_epoch.period);
+    
+    msg!(
+        "Revenue epoch {} closed out for track: {}",
+        revenue_epoch.period,
+        context.accounts.master_nft.title
+    );
     Ok(())
 }
 
+pub(crate) fn initialize_revenue_epoch(
+    revenue_epoch: &mut RevenueEpoch,
+    master_nft: Pubkey,
+    period: u32,
+    bump: u8,
+    payer: Pubkey,
+    timestamp: i64,
+) {
+    revenue_epoch.master_nft = master_nft;
+    revenue_epoch.period = period;
+    revenue_epoch.total_revenue = 0;
+    revenue_epoch.streaming_revenue = 0;
+    revenue_epoch.sales_revenue = 0;
+    revenue_epoch.other_revenue = 0;
+    revenue_epoch.total_claimed = 0;
+    revenue_epoch.is_closed = false;
+    revenue_epoch.created_at = timestamp;
+    revenue_epoch.closed_at = 0;
+    revenue_epoch.bump = bump;
+    revenue_epoch.claimed_basis_points = 0;
+    revenue_epoch.statement_count = 0;
+    revenue_epoch.payer = payer;
+}
+
+// Streaming batches cannot declare their epochs as `init_if_needed`, so an epoch is
+// created here on the first revenue of its period and counted on the track's split.
+// The caller serializes both afterwards.
+pub(crate) fn load_or_create_revenue_epoch<'info>(
+    revenue_epoch_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    royalty_split: &mut RoyaltySplit,
+    master_nft: Pubkey,
+    period: u32,
+    timestamp: i64,
+) -> Result<RevenueEpoch> {
+    require!(royalty_split.accepts_period(period), CustomError::EpochClosed);
+    
+    if revenue_epoch_info.owner == &crate::ID && !revenue_epoch_info.data_is_empty() {
+        return RevenueEpoch::try_deserialize(&mut &revenue_epoch_info.try_borrow_data()?[..]);
+    }
+    
+    let (address, bump) = Pubkey::find_program_address(
+        &[REVENUE_EPOCH_SEED, master_nft.as_ref(), &period.to_le_bytes()],
+        &crate::ID
+    );
+    require!(revenue_epoch_info.key() == address, CustomError::EpochNotFound);
+    
+    create_program_account(
+        revenue_epoch_info,
+        payer,
+        system_program,
+        &[REVENUE_EPOCH_SEED, master_nft.as_ref(), &period.to_le_bytes(), &[bump]],
+        RevenueEpoch::INIT_SPACE,
+    )?;
+    
+    royalty_split.revenue_epoch_count = royalty_split.revenue_epoch_count.checked_add(1).unwrap();
+    
+    let mut revenue_epoch = RevenueEpoch::default();
+    initialize_revenue_epoch(&mut revenue_epoch, master_nft, period, bump, payer.key(), timestamp);
+    Ok(revenue_epoch)
+}
+
 #[derive(Accounts)]
+#[instruction(amount: u64, source: String, description: String, period: u32)]
 pub struct TrackRevenueAccountConstraints<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -135,11 +387,28 @@ pub struct TrackRevenueAccountConstraints<'info> {
     )]
     pub master_nft: Account<'info, MasterNft>,
     
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Artist's label, required for roster artists so their revenue is aggregated
+    #[account(
+        mut,
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
     #[account(
         mut,
         seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
         bump = royalty_split.bump,
-        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit
+        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit,
+        constraint = royalty_split.revenue_migrated @ CustomError::RoyaltySplitNotMigrated
     )]
     pub royalty_split: Account<'info, RoyaltySplit>,
     
@@ -152,6 +421,60 @@ pub struct TrackRevenueAccountConstraints<'info> {
     )]
     pub revenue_tracker: Account<'info, RevenueTracker>,
     
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RevenueEpoch::INIT_SPACE,
+        seeds = [REVENUE_EPOCH_SEED, master_nft.key().as_ref(), &period.to_le_bytes()],
+        bump
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u32)]
+pub struct OpenRevenueEpochAccountConstraints<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump
+    )]
+    pub master_nft: Account<'info, MasterNft>,
+    
+    #[account(
+        mut,
+        seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
+        bump = royalty_split.bump,
+        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit,
+        constraint = royalty_split.revenue_migrated @ CustomError::RoyaltySplitNotMigrated
+    )]
+    pub royalty_split: Account<'info, RoyaltySplit>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = RevenueEpoch::INIT_SPACE,
+        seeds = [REVENUE_EPOCH_SEED, master_nft.key().as_ref(), &period.to_le_bytes()],
+        bump
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+    
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -181,22 +504,105 @@ pub struct ClaimRevenueAccountConstraints<'info> {
     )]
     pub royalty_split: Account<'info, RoyaltySplit>,
     
+    // Artist who owns the track, whose fee tier applies to the claim
     #[account(
-        seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::InvalidData
     )]
-    pub treasury: Account<'info, Treasury>,
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    /// CHECK: Per-artist fee override address, uninitialized when the artist has no override.
+    /// Required so a claimer cannot leave out an override that raises their fee.
+    #[account(
+        seeds = [FEE_OVERRIDE_SEED, artist_profile.key().as_ref()],
+        bump
+    )]
+    pub fee_override: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [REVENUE_EPOCH_SEED, master_nft.key().as_ref(), &revenue_epoch.period.to_le_bytes()],
+        bump = revenue_epoch.bump,
+        constraint = revenue_epoch.master_nft == master_nft.key() @ CustomError::InvalidData
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RoyaltyStatement::INIT_SPACE,
+        seeds = [ROYALTY_STATEMENT_SEED, revenue_epoch.key().as_ref(), royalty_nft.key().as_ref()],
+        bump
+    )]
+    pub royalty_statement: Account<'info, RoyaltyStatement>,
     
-    /// CHECK: Treasury wallet account
     #[account(
         mut,
-        constraint = treasury_wallet.key() == treasury.treasury_wallet @ CustomError::Unauthorized
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
     )]
-    pub treasury_wallet: UncheckedAccount<'info>,
+    pub treasury: Account<'info, Treasury>,
     
     /// CHECK: Payer account for revenue distribution (program or exchange)
     #[account(mut)]
     pub payer: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRevenueEpochAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
+    )]
+    pub artist_profile: Box<Account<'info, ArtistProfile>>,
+    
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, when a label manager is acting for a roster artist
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
+    #[account(
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump,
+        constraint = master_nft.artist_profile == artist_profile.key() @ CustomError::Unauthorized
+    )]
+    pub master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        mut,
+        seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
+        bump = royalty_split.bump,
+        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit
+    )]
+    pub royalty_split: Box<Account<'info, RoyaltySplit>>,
+    
+    #[account(
+        mut,
+        close = payer,
+        seeds = [REVENUE_EPOCH_SEED, master_nft.key().as_ref(), &revenue_epoch.period.to_le_bytes()],
+        bump = revenue_epoch.bump,
+        constraint = revenue_epoch.master_nft == master_nft.key() @ CustomError::InvalidData,
+        constraint = revenue_epoch.payer == payer.key() @ CustomError::InvalidData
+    )]
+    pub revenue_epoch: Account<'info, RevenueEpoch>,
+    
+    /// CHECK: Paid the epoch's rent and receives it back, checked against the epoch
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
 }
\ No newline at end of file
@@ -1,14 +1,47 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token::{
+    burn, close_account, mint_to, set_authority,
+    spl_token::instruction::AuthorityType,
+    Burn, CloseAccount, Mint, MintTo, SetAuthority, Token, TokenAccount,
+};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::error::CustomError;
 use crate::constants::*;
 
-pub fn create_royalty_split(
+pub(crate) fn create_royalty_split(
     context: Context<CreateRoyaltySplitAccountConstraints>,
     collaborators: Vec<Collaborator>
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_ROYALTY_ISSUANCE), CustomError::Paused);
+    require!(
+        ArtistDelegate::has_access(
+            &context.accounts.artist_profile,
+            &context.accounts.authority.key(),
+            context.accounts.delegate.as_deref(),
+            context.accounts.label.as_deref(),
+            DELEGATE_CREATE_SPLITS
+        ),
+        CustomError::Unauthorized
+    );
+    
+    // Roster artists carry their label's default participation into every new split
+    let mut collaborators = collaborators;
+    if context.accounts.artist_profile.label.is_some() {
+        let label = context.accounts.label
+            .as_ref()
+            .ok_or(CustomError::LabelRequired)?;
+        
+        if label.default_share_basis_points > 0 {
+            collaborators.push(Collaborator {
+                address: label.royalty_wallet,
+                name: label.name.clone(),
+                share_basis_points: label.default_share_basis_points,
+                amount_claimed: 0,
+            });
+        }
+    }
+    
     // Validate collaborators
     require!(!collaborators.is_empty(), CustomError::InvalidRoyaltySplit);
     require!(collaborators.len() <= 10, CustomError::TooManyCollaborators);
@@ -35,15 +68,24 @@ pub fn create_royalty_split(
     royalty_split.created_at = clock.unix_timestamp;
     royalty_split.last_revenue_timestamp = 0;
     royalty_split.bump = context.bumps.royalty_split;
+    royalty_split.revenue_migrated = true;
+    royalty_split.revenue_epoch_count = 0;
+    royalty_split.royalty_nft_count = 0;
+    royalty_split.minted_basis_points = 0;
+    royalty_split.upstream = Vec::new();
+    royalty_split.closed_through_period = 0;
+    royalty_split.minted_shares = Vec::new();
     
     msg!("Royalty split created for track: {}", context.accounts.master_nft.title);
     Ok(())
 }
 
-pub fn mint_royalty_nft(
+pub(crate) fn mint_royalty_nft(
     context: Context<MintRoyaltyNftAccountConstraints>,
     share_basis_points: u16
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_ROYALTY_ISSUANCE), CustomError::Paused);
+    
     let royalty_split = &mut context.accounts.royalty_split;
     
     // Validate share allocation doesn't exceed remaining shares
@@ -54,7 +96,22 @@ pub fn mint_royalty_nft(
         }
     }
     
-    require!(share_basis_points <= allocated_share, CustomError::InvalidRoyaltyShares);
+    // Every royalty NFT the collaborator has minted counts against their allocation
+    let collaborator = context.accounts.authority.key();
+    let minted_share = royalty_split.minted_share_of(&collaborator)
+        .checked_add(share_basis_points).unwrap();
+    require!(
+        share_basis_points > 0 && minted_share <= allocated_share,
+        CustomError::InvalidRoyaltyShares
+    );
+    royalty_split.record_minted_share(collaborator, share_basis_points);
+    
+    // Outstanding royalty NFTs can never hold more than the whole track
+    let minted_basis_points = royalty_split.minted_basis_points
+        .checked_add(share_basis_points).unwrap();
+    require!(minted_basis_points <= TOTAL_BASIS_POINTS, CustomError::InvalidRoyaltyShares);
+    royalty_split.minted_basis_points = minted_basis_points;
+    royalty_split.royalty_nft_count = royalty_split.royalty_nft_count.checked_add(1).unwrap();
     
     // Create royalty NFT
     let royalty_nft = &mut context.accounts.royalty_nft;
@@ -68,10 +125,87 @@ pub fn mint_royalty_nft(
     royalty_nft.created_at = clock.unix_timestamp;
     royalty_nft.bump = context.bumps.royalty_nft;
     
+    mint_royalty_token(&context)?;
+    
     msg!("Royalty NFT minted with {}% share", share_basis_points as f32 / 100.0);
     Ok(())
 }
 
+// Burns the holder's royalty token and gives its share back to the track. Revenue the
+// holder has not claimed yet is forfeited.
+pub(crate) fn close_royalty_nft(
+    context: Context<CloseRoyaltyNftAccountConstraints>
+) -> Result<()> {
+    // Burning hands the share back to the track's outstanding total, so it has to wait until
+    // every epoch is settled and closed out. Otherwise a claimed share could be minted again
+    // and claimed twice from the same epoch.
+    require!(
+        context.accounts.royalty_split.revenue_epoch_count == 0,
+        CustomError::UnclaimedRevenue
+    );
+    
+    burn(
+        CpiContext::new(
+            context.accounts.token_program.to_account_info(),
+            Burn {
+                mint: context.accounts.mint.to_account_info(),
+                from: context.accounts.token_account.to_account_info(),
+                authority: context.accounts.authority.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    close_account(CpiContext::new(
+        context.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: context.accounts.token_account.to_account_info(),
+            destination: context.accounts.authority.to_account_info(),
+            authority: context.accounts.authority.to_account_info(),
+        },
+    ))?;
+    
+    let royalty_split = &mut context.accounts.royalty_split;
+    royalty_split.royalty_nft_count = royalty_split.royalty_nft_count.checked_sub(1).unwrap();
+    royalty_split.minted_basis_points = royalty_split.minted_basis_points
+        .saturating_sub(context.accounts.royalty_nft.share_basis_points);
+    
+    msg!("Royalty NFT closed for track: {}", context.accounts.master_nft.title);
+    Ok(())
+}
+
+// Mints the single royalty token and revokes mint authority so supply is fixed at 1
+fn mint_royalty_token(context: &Context<MintRoyaltyNftAccountConstraints>) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[NFT_AUTHORITY_SEED, &[context.bumps.nft_authority]]];
+    
+    mint_to(
+        CpiContext::new_with_signer(
+            context.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: context.accounts.mint.to_account_info(),
+                to: context.accounts.token_account.to_account_info(),
+                authority: context.accounts.nft_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+    
+    set_authority(
+        CpiContext::new_with_signer(
+            context.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: context.accounts.nft_authority.to_account_info(),
+                account_or_mint: context.accounts.mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CreateRoyaltySplitAccountConstraints<'info> {
     #[account(mut)]
@@ -79,12 +213,26 @@ pub struct CreateRoyaltySplitAccountConstraints<'info> {
     
     #[account(
         mut,
-        seeds = [ARTIST_PROFILE_SEED, authority.key().as_ref()],
-        bump,
-        constraint = artist_profile.authority == authority.key() @ CustomError::Unauthorized
+        seeds = [ARTIST_PROFILE_SEED, artist_profile.creator.as_ref()],
+        bump = artist_profile.bump
     )]
     pub artist_profile: Account<'info, ArtistProfile>,
     
+    // Signer's delegation from the artist, when acting as a manager rather than the artist
+    #[account(
+        seeds = [ARTIST_DELEGATE_SEED, artist_profile.key().as_ref(), authority.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, ArtistDelegate>>,
+    
+    // Artist's label, required for roster artists so its default share is included
+    #[account(
+        seeds = [LABEL_SEED, label.authority.as_ref()],
+        bump = label.bump,
+        constraint = artist_profile.label == Some(label.key()) @ CustomError::LabelRequired
+    )]
+    pub label: Option<Box<Account<'info, Label>>>,
+    
     #[account(
         seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
         bump = master_nft.bump,
@@ -101,6 +249,12 @@ pub struct CreateRoyaltySplitAccountConstraints<'info> {
     )]
     pub royalty_split: Account<'info, RoyaltySplit>,
     
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -119,7 +273,8 @@ pub struct MintRoyaltyNftAccountConstraints<'info> {
         mut,
         seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
         bump = royalty_split.bump,
-        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit
+        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit,
+        constraint = royalty_split.revenue_migrated @ CustomError::RoyaltySplitNotMigrated
     )]
     pub royalty_split: Box<Account<'info, RoyaltySplit>>,
     
@@ -128,11 +283,18 @@ pub struct MintRoyaltyNftAccountConstraints<'info> {
         init,
         payer = authority,
         mint::decimals = 0,
-        mint::authority = authority,
-        mint::freeze_authority = authority,
+        mint::authority = nft_authority,
+        mint::freeze_authority = nft_authority,
     )]
     pub mint: Account<'info, Mint>,
     
+    /// CHECK: Program PDA that mints the single royalty token before revoking itself
+    #[account(
+        seeds = [NFT_AUTHORITY_SEED],
+        bump
+    )]
+    pub nft_authority: UncheckedAccount<'info>,
+    
     // Associated token account for the NFT
     #[account(
         init_if_needed,
@@ -151,9 +313,61 @@ pub struct MintRoyaltyNftAccountConstraints<'info> {
     )]
     pub royalty_nft: Box<Account<'info, RoyaltyNft>>,
     
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
     // Required programs
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRoyaltyNftAccountConstraints<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [MASTER_NFT_SEED, master_nft.mint.as_ref()],
+        bump = master_nft.bump
+    )]
+    pub master_nft: Box<Account<'info, MasterNft>>,
+    
+    #[account(
+        mut,
+        seeds = [ROYALTY_SPLIT_SEED, master_nft.key().as_ref()],
+        bump = royalty_split.bump,
+        constraint = royalty_split.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit,
+        constraint = royalty_split.revenue_migrated @ CustomError::RoyaltySplitNotMigrated
+    )]
+    pub royalty_split: Box<Account<'info, RoyaltySplit>>,
+    
+    #[account(
+        mut,
+        constraint = royalty_nft.mint == mint.key() @ CustomError::InvalidData
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    // Holder's token account, which is emptied and closed
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ROYALTY_NFT_SEED, mint.key().as_ref()],
+        bump = royalty_nft.bump,
+        constraint = royalty_nft.master_nft == master_nft.key() @ CustomError::InvalidRoyaltySplit
+    )]
+    pub royalty_nft: Box<Account<'info, RoyaltyNft>>,
+    
+    pub token_program: Program<'info, Token>,
 }
\ No newline at end of file
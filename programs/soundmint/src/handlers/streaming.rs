@@ -2,16 +2,20 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::CustomError;
 use crate::constants::*;
+use crate::handlers::derivative::{cascade_upstream_revenue, record_label_revenue};
+use crate::handlers::revenue::load_or_create_revenue_epoch;
 
-pub fn register_streaming_batch(
-    context: Context<RegisterStreamingBatchAccountConstraints>,
+pub(crate) fn register_streaming_batch<'info>(
+    context: Context<'_, '_, '_, 'info, RegisterStreamingBatchAccountConstraints<'info>>,
     streaming_data: Vec<StreamingData>
 ) -> Result<()> {
+    require!(!context.accounts.treasury.is_paused(PAUSE_STREAMING), CustomError::Paused);
     require!(!streaming_data.is_empty(), CustomError::InvalidData);
     require!(streaming_data.len() <= MAX_STREAMING_BATCH_SIZE, CustomError::BatchTooLarge);
     
     let clock = Clock::get()?;
     let treasury = &mut context.accounts.treasury;
+    treasury.apply_pending_config(clock.unix_timestamp);
     
     // Only authorized streaming provider can register streaming data
     require!(
@@ -21,56 +25,65 @@ pub fn register_streaming_batch(
     
     let mut total_platform_fee : u64 = 0;
     
+    let current_period = RevenueEpoch::period_for_timestamp(clock.unix_timestamp);
+    
     // Process each streaming record
     for record in &streaming_data {
         require!(record.amount > 0, CustomError::InvalidAmount);
+        require!(
+            RevenueEpoch::is_valid_period(record.period) && record.period <= current_period,
+            CustomError::InvalidEpochPeriod
+        );
         
-        // Get revenue tracker for this master NFT
-        let seeds = &[
-            REVENUE_TRACKER_SEED,
-            record.master_nft.as_ref(),
-            &[record.bump]
-        ];
+        let revenue_tracker_address = Pubkey::find_program_address(
+            &[REVENUE_TRACKER_SEED, record.master_nft.as_ref()],
+            &crate::ID
+        ).0;
+        let royalty_split_address = Pubkey::find_program_address(
+            &[ROYALTY_SPLIT_SEED, record.master_nft.as_ref()],
+            &crate::ID
+        ).0;
         
-        // Create CPI context for revenue tracker
+        // Find the accounts for this master NFT among the remaining accounts
         let revenue_tracker_account_info = context.remaining_accounts.iter().find(|account| {
-            account.key() == Pubkey::find_program_address(
-                &[REVENUE_TRACKER_SEED, record.master_nft.as_ref()],
-                &crate::ID
-            ).0
+            account.key() == revenue_tracker_address
         });
         
         let royalty_split_account_info = context.remaining_accounts.iter().find(|account| {
-            account.key() == Pubkey::find_program_address(
-                &[ROYALTY_SPLIT_SEED, record.master_nft.as_ref()],
-                &crate::ID
-            ).0
+            account.key() == royalty_split_address
         });
         
         if let (Some(revenue_tracker_info), Some(royalty_split_info)) = (revenue_tracker_account_info, royalty_split_account_info) {
-            // Calculate platform fee
+            // Streaming revenue lands in the period's settlement epoch, which is opened on first use
+            let revenue_epoch_address = Pubkey::find_program_address(
+                &[REVENUE_EPOCH_SEED, record.master_nft.as_ref(), &record.period.to_le_bytes()],
+                &crate::ID
+            ).0;
+            let revenue_epoch_info = context.remaining_accounts
+                .iter()
+                .find(|account| account.key() == revenue_epoch_address)
+                .ok_or(CustomError::EpochNotFound)?;
+            
+            // Calculate platform fee at the artist's fee tier
+            let (platform_fee_basis_points, artist_label) = resolve_platform_fee_basis_points(
+                treasury,
+                context.remaining_accounts,
+                &record.master_nft,
+                clock.unix_timestamp,
+            )?;
             let platform_fee: u64 = record.amount
-                .checked_mul(treasury.platform_fee_basis_points as u64).unwrap()
+                .checked_mul(platform_fee_basis_points as u64).unwrap()
                 .checked_div(TOTAL_BASIS_POINTS as u64).unwrap();
             
             total_platform_fee = total_platform_fee.checked_add(platform_fee).unwrap();
             
             // Update revenue tracker
             let mut revenue_tracker_data = revenue_tracker_info.try_borrow_mut_data()?;
-            let revenue_tracker = RevenueTracker::try_deserialize(&mut &revenue_tracker_data[..])?;
+            let mut revenue_tracker = RevenueTracker::try_deserialize(&mut &revenue_tracker_data[..])?;
             
-            // Get mutable reference to the revenue tracker
-            let mut revenue_tracker = RevenueTracker {
-                master_nft: revenue_tracker.master_nft,
-                total_revenue: revenue_tracker.total_revenue.checked_add(record.amount).unwrap(),
-                streaming_revenue: revenue_tracker.streaming_revenue.checked_add(record.amount).unwrap(),
-                sales_revenue: revenue_tracker.sales_revenue,
-                other_revenue: revenue_tracker.other_revenue,
-                transactions: revenue_tracker.transactions.clone(),
-                created_at: revenue_tracker.created_at,
-                last_revenue_timestamp: clock.unix_timestamp,
-                bump: revenue_tracker.bump,
-            };
+            revenue_tracker.total_revenue = revenue_tracker.total_revenue.checked_add(record.amount).unwrap();
+            revenue_tracker.streaming_revenue = revenue_tracker.streaming_revenue.checked_add(record.amount).unwrap();
+            revenue_tracker.last_revenue_timestamp = clock.unix_timestamp;
             
             // Add transaction record
             if revenue_tracker.transactions.len() < RevenueTracker::MAX_TRANSACTIONS {
@@ -87,21 +100,49 @@ pub fn register_streaming_batch(
             
             // Update royalty split
             let mut royalty_split_data = royalty_split_info.try_borrow_mut_data()?;
-            let royalty_split = RoyaltySplit::try_deserialize(&mut &royalty_split_data[..])?;
+            let mut royalty_split = RoyaltySplit::try_deserialize(&mut &royalty_split_data[..])?;
+            require!(royalty_split.revenue_migrated, CustomError::RoyaltySplitNotMigrated);
             
-            // Get mutable reference to the royalty split
-            let mut royalty_split = RoyaltySplit {
-                master_nft: royalty_split.master_nft,
-                collaborators: royalty_split.collaborators.clone(),
-                total_basis_points: royalty_split.total_basis_points,
-                total_revenue_collected: royalty_split.total_revenue_collected.checked_add(record.amount).unwrap(),
-                created_at: royalty_split.created_at,
-                last_revenue_timestamp: clock.unix_timestamp,
-                bump: royalty_split.bump,
-            };
+            // Parents of a derivative take their agreed cut before the track's own holders
+            let upstream_revenue = cascade_upstream_revenue(
+                &mut royalty_split,
+                record.amount,
+                "streaming",
+                record.period,
+                context.remaining_accounts,
+                &context.accounts.streaming_provider.to_account_info(),
+                &context.accounts.system_program.to_account_info(),
+            )?;
+            let retained = upstream_revenue.retained;
             
-            // Reserialize the royalty split
+            royalty_split.total_revenue_collected = royalty_split.total_revenue_collected.checked_add(retained).unwrap();
+            royalty_split.last_revenue_timestamp = clock.unix_timestamp;
+            
+            // Update revenue epoch
+            let mut revenue_epoch = load_or_create_revenue_epoch(
+                revenue_epoch_info,
+                &context.accounts.streaming_provider.to_account_info(),
+                &context.accounts.system_program.to_account_info(),
+                &mut royalty_split,
+                record.master_nft,
+                record.period,
+                clock.unix_timestamp,
+            )?;
+            
+            require!(!revenue_epoch.is_closed, CustomError::EpochClosed);
+            revenue_epoch.record_revenue(retained, "streaming");
+            
+            // Reserialize the revenue epoch and royalty split
+            revenue_epoch.try_serialize(&mut *revenue_epoch_info.try_borrow_mut_data()?)?;
             royalty_split.try_serialize(&mut *royalty_split_data)?;
+            
+            // Roll roster revenue up into the artist's label, and each parent's cut into theirs
+            if let Some(artist_label) = artist_label {
+                record_label_revenue(context.remaining_accounts, artist_label, retained)?;
+            }
+            for (parent_label, upstream_amount) in upstream_revenue.label_revenue {
+                record_label_revenue(context.remaining_accounts, parent_label, upstream_amount)?;
+            }
         }
     }
     
@@ -109,7 +150,7 @@ pub fn register_streaming_batch(
     if total_platform_fee > 0 {
         let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
             &context.accounts.streaming_provider.key(),
-            &treasury.treasury_wallet,
+            &treasury.key(),
             total_platform_fee
         );
         
@@ -117,24 +158,67 @@ pub fn register_streaming_batch(
             &transfer_instruction,
             &[
                 context.accounts.streaming_provider.to_account_info(),
-                context.accounts.treasury_wallet.to_account_info(),
+                treasury.to_account_info(),
                 context.accounts.system_program.to_account_info(),
             ]
         )?;
         
         // Update treasury revenue
-        treasury.total_revenue_collected = treasury.total_revenue_collected.checked_add(total_platform_fee).unwrap();
+        treasury.record_fee(FeeCategory::Streaming, total_platform_fee);
     }
     
     msg!("Processed streaming batch with {} records", streaming_data.len());
     Ok(())
 }
 
+// The master NFT, its artist profile and the artist's fee override address must all be
+// supplied so a provider cannot pick a cheaper schedule by leaving accounts out.
+// Also rejects tracks that are not earning and returns the artist's label so its revenue
+// can be aggregated.
+fn resolve_platform_fee_basis_points(
+    treasury: &Treasury,
+    remaining_accounts: &[AccountInfo],
+    master_nft: &Pubkey,
+    now: i64,
+) -> Result<(u16, Option<Pubkey>)> {
+    let find_account = |address: &Pubkey| {
+        remaining_accounts
+            .iter()
+            .find(|account| account.key() == *address && account.owner == &crate::ID)
+            .ok_or(CustomError::FeeAccountNotFound)
+    };
+    
+    let master_nft_info = find_account(master_nft)?;
+    let master_nft = MasterNft::try_deserialize(&mut &master_nft_info.try_borrow_data()?[..])?;
+    require!(master_nft.status.accepts_revenue(), CustomError::TrackNotEarning);
+    
+    let artist_profile_info = find_account(&master_nft.artist_profile)?;
+    let artist_profile = ArtistProfile::try_deserialize(&mut &artist_profile_info.try_borrow_data()?[..])?;
+    
+    let fee_override_address = Pubkey::find_program_address(
+        &[FEE_OVERRIDE_SEED, master_nft.artist_profile.as_ref()],
+        &crate::ID
+    ).0;
+    let fee_override_info = remaining_accounts
+        .iter()
+        .find(|account| account.key() == fee_override_address)
+        .ok_or(CustomError::FeeAccountNotFound)?;
+    
+    let fee_override = FeeOverride::load(fee_override_info)?;
+    
+    let platform_fee_basis_points = treasury
+        .fee_schedule(artist_profile.is_verified, fee_override.as_ref(), now)
+        .platform_fee_basis_points;
+    
+    Ok((platform_fee_basis_points, artist_profile.label))
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct StreamingData {
     pub master_nft: Pubkey,
     pub amount: u64,
     pub bump: u8,
+    pub period: u32,
 }
 
 #[derive(Accounts)]
@@ -152,12 +236,5 @@ pub struct RegisterStreamingBatchAccountConstraints<'info> {
     )]
     pub treasury: Account<'info, Treasury>,
     
-    /// CHECK: Treasury wallet for receiving fees
-    #[account(
-        mut,
-        constraint = treasury_wallet.key() == treasury.treasury_wallet @ CustomError::InvalidTreasuryWallet
-    )]
-    pub treasury_wallet: UncheckedAccount<'info>,
-    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
+// Anchor 0.30 macros test cfgs (custom-heap, anchor-debug, target_os = "solana") this crate does not declare
+#![allow(unexpected_cfgs)]
+
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod handlers;
 pub mod state;
 
@@ -7,6 +11,7 @@ use anchor_lang::prelude::*;
 
 pub use constants::*;
 pub use error::*;
+pub use events::*;
 pub use handlers::*;
 pub use state::*;
 
@@ -25,8 +30,9 @@ pub mod soundmint {
         name: String,
         description: String,
         profile_image_uri: String,
+        referrer: Option<Pubkey>,
     ) -> Result<()> {
-        artist::create_artist_profile(context, name, description, profile_image_uri)
+        artist::create_artist_profile(context, name, description, profile_image_uri, referrer)
     }
 
     pub fn update_artist_profile(
@@ -39,13 +45,62 @@ pub mod soundmint {
         artist::update_artist_profile(context, name, description, profile_image_uri, social_links)
     }
 
+    pub fn propose_artist_profile_transfer(
+        context: Context<UpdateArtistProfileAuthorityAccountConstraints>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        artist::propose_artist_profile_transfer(context, new_authority)
+    }
+
+    pub fn accept_artist_profile_transfer(
+        context: Context<AcceptArtistProfileTransferAccountConstraints>,
+    ) -> Result<()> {
+        artist::accept_artist_profile_transfer(context)
+    }
+
+    pub fn cancel_artist_profile_transfer(
+        context: Context<UpdateArtistProfileAuthorityAccountConstraints>,
+    ) -> Result<()> {
+        artist::cancel_artist_profile_transfer(context)
+    }
+
+    pub fn set_artist_delegate(
+        context: Context<SetArtistDelegateAccountConstraints>,
+        permissions: u8,
+    ) -> Result<()> {
+        artist::set_artist_delegate(context, permissions)
+    }
+
+    pub fn remove_artist_delegate(context: Context<RemoveArtistDelegateAccountConstraints>) -> Result<()> {
+        artist::remove_artist_delegate(context)
+    }
+
+    pub fn submit_verification_request(
+        context: Context<SubmitVerificationRequestAccountConstraints>,
+        evidence_uri: String,
+        requested_tier: VerificationTier,
+    ) -> Result<()> {
+        artist::submit_verification_request(context, evidence_uri, requested_tier)
+    }
+
+    pub fn close_artist_profile(context: Context<CloseArtistProfileAccountConstraints>) -> Result<()> {
+        artist::close_artist_profile(context)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_master_nft(
         context: Context<MintMasterNftAccountConstraints>,
         title: String,
         description: String,
         audio_uri: String,
         artwork_uri: String,
+        audio_hash: [u8; 32],
+        artwork_hash: [u8; 32],
+        audio_spec: AudioSpec,
         metadata: Vec<MetadataItem>,
+        isrc: Option<String>,
+        fingerprint: [u8; 32],
+        version_kind: Option<VersionKind>,
     ) -> Result<()> {
         nft::mint_master_nft(
             context,
@@ -53,18 +108,37 @@ pub mod soundmint {
             description,
             audio_uri,
             artwork_uri,
+            audio_hash,
+            artwork_hash,
+            audio_spec,
             metadata,
+            isrc,
+            fingerprint,
+            version_kind,
         )
     }
 
+    pub fn create_nft_metadata(
+        context: Context<CreateMetadataAccountConstraints>,
+        metadata_uri: String,
+        seller_fee_basis_points: u16,
+    ) -> Result<()> {
+        nft::create_nft_metadata(context, metadata_uri, seller_fee_basis_points)
+    }
+
     pub fn update_master_nft(
         context: Context<UpdateMasterNftAccountConstraints>,
         description: Option<String>,
         metadata: Option<Vec<MetadataItem>>,
         is_transferable: Option<bool>,
         status: Option<MasterNftStatus>,
+        metadata_uri: Option<String>,
     ) -> Result<()> {
-        nft::update_master_nft(context, description, metadata, is_transferable, status)
+        nft::update_master_nft(context, description, metadata, is_transferable, status, metadata_uri)
+    }
+
+    pub fn close_master_nft(context: Context<CloseMasterNftAccountConstraints>) -> Result<()> {
+        nft::close_master_nft(context)
     }
 
     pub fn create_royalty_split(
@@ -81,45 +155,143 @@ pub mod soundmint {
         royalty::mint_royalty_nft(context, share_basis_points)
     }
 
-    pub fn track_revenue(
-        context: Context<TrackRevenueAccountConstraints>,
+    pub fn close_royalty_nft(context: Context<CloseRoyaltyNftAccountConstraints>) -> Result<()> {
+        royalty::close_royalty_nft(context)
+    }
+
+    pub fn declare_derivative(
+        context: Context<DeclareDerivativeAccountConstraints>,
+        kind: DerivativeKind,
+        upstream_basis_points: u16,
+    ) -> Result<()> {
+        derivative::declare_derivative(context, kind, upstream_basis_points)
+    }
+
+    pub fn accept_derivative(context: Context<AcceptDerivativeAccountConstraints>) -> Result<()> {
+        derivative::accept_derivative(context)
+    }
+
+    pub fn reject_derivative(context: Context<RejectDerivativeAccountConstraints>) -> Result<()> {
+        derivative::reject_derivative(context)
+    }
+
+    pub fn track_revenue<'info>(
+        context: Context<'_, '_, '_, 'info, TrackRevenueAccountConstraints<'info>>,
         amount: u64,
         source: String,
         description: String,
+        period: u32,
+    ) -> Result<()> {
+        revenue::track_revenue(context, amount, source, description, period)
+    }
+
+    pub fn open_revenue_epoch(
+        context: Context<OpenRevenueEpochAccountConstraints>,
+        period: u32,
     ) -> Result<()> {
-        revenue::track_revenue(context, amount, source, description)
+        revenue::open_revenue_epoch(context, period)
+    }
+
+    pub fn close_epoch(context: Context<CloseEpochAccountConstraints>) -> Result<()> {
+        admin::epoch::close_epoch(context)
+    }
+
+    pub fn migrate_royalty_split(context: Context<MigrateRoyaltySplitAccountConstraints>) -> Result<()> {
+        admin::epoch::migrate_royalty_split(context)
+    }
+
+    pub fn migrate_treasury(context: Context<MigrateTreasuryAccountConstraints>) -> Result<()> {
+        admin::migration::migrate_treasury(context)
+    }
+
+    pub fn migrate_artist_profile(context: Context<MigrateArtistProfileAccountConstraints>) -> Result<()> {
+        admin::migration::migrate_artist_profile(context)
+    }
+
+    pub fn migrate_master_nft(context: Context<MigrateMasterNftAccountConstraints>) -> Result<()> {
+        admin::migration::migrate_master_nft(context)
     }
 
     pub fn claim_revenue(context: Context<ClaimRevenueAccountConstraints>) -> Result<()> {
         revenue::claim_revenue(context)
     }
 
+    pub fn close_revenue_epoch<'info>(
+        context: Context<'_, '_, '_, 'info, CloseRevenueEpochAccountConstraints<'info>>,
+    ) -> Result<()> {
+        revenue::close_revenue_epoch(context)
+    }
+
     pub fn update_treasury_config(
         context: Context<UpdateTreasuryConfigAccountConstraints>,
         mint_fee: Option<u64>,
         platform_fee_basis_points: Option<u16>,
+        verified_mint_fee: Option<u64>,
+        verified_platform_fee_basis_points: Option<u16>,
         new_treasury_wallet: Option<Pubkey>,
     ) -> Result<()> {
-        admin::treasury::update_treasury_config(
+        admin::treasury_management::update_treasury_config(
             context,
             mint_fee,
             platform_fee_basis_points,
+            verified_mint_fee,
+            verified_platform_fee_basis_points,
             new_treasury_wallet,
         )
     }
 
+    pub fn cancel_treasury_config(
+        context: Context<UpdateTreasuryConfigAccountConstraints>,
+    ) -> Result<()> {
+        admin::treasury_management::cancel_treasury_config(context)
+    }
+
     pub fn update_streaming_provider(
         context: Context<UpdateTreasuryConfigAccountConstraints>,
         new_streaming_provider: Pubkey,
     ) -> Result<()> {
-        admin::treasury::update_streaming_provider(context, new_streaming_provider)
+        admin::treasury_management::update_streaming_provider(context, new_streaming_provider)
+    }
+
+    pub fn propose_treasury_authority(
+        context: Context<UpdateTreasuryConfigAccountConstraints>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        admin::treasury_management::propose_treasury_authority(context, new_authority)
+    }
+
+    pub fn accept_treasury_authority(
+        context: Context<AcceptTreasuryAuthorityAccountConstraints>,
+    ) -> Result<()> {
+        admin::treasury_management::accept_treasury_authority(context)
+    }
+
+    pub fn cancel_treasury_authority_transfer(
+        context: Context<UpdateTreasuryConfigAccountConstraints>,
+    ) -> Result<()> {
+        admin::treasury_management::cancel_treasury_authority_transfer(context)
     }
 
     pub fn withdraw_treasury_funds(
         context: Context<WithdrawTreasuryFundsAccountConstraints>,
+        category: FeeCategory,
         amount: u64,
     ) -> Result<()> {
-        admin::treasury::withdraw_treasury_funds(context, amount)
+        admin::treasury_management::withdraw_treasury_funds(context, category, amount)
+    }
+
+    pub fn set_pause_flags(
+        context: Context<SetPauseFlagsAccountConstraints>,
+        paused_flags: u8,
+    ) -> Result<()> {
+        admin::pause::set_pause_flags(context, paused_flags)
+    }
+
+    pub fn set_guardian(
+        context: Context<SetGuardianAccountConstraints>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        admin::pause::set_guardian(context, new_guardian)
     }
 
     pub fn verify_artist(
@@ -129,20 +301,177 @@ pub mod soundmint {
         admin::artist::verify_artist(context, verify)
     }
 
-    pub fn register_streaming_batch(
-        context: Context<RegisterStreamingBatchAccountConstraints>,
+    pub fn review_verification_request(
+        context: Context<ReviewVerificationRequestAccountConstraints>,
+        approve: bool,
+        tier: Option<VerificationTier>,
+        reason_code: u8,
+    ) -> Result<()> {
+        admin::artist::review_verification_request(context, approve, tier, reason_code)
+    }
+
+    pub fn moderate_master_nft(
+        context: Context<ModerateMasterNftAccountConstraints>,
+        status: MasterNftStatus,
+        reason_code: u8,
+    ) -> Result<()> {
+        admin::moderation::moderate_master_nft(context, status, reason_code)
+    }
+
+    pub fn reassign_isrc(context: Context<ReassignIsrcAccountConstraints>, reason_code: u8) -> Result<()> {
+        admin::identifiers::reassign_isrc(context, reason_code)
+    }
+
+    pub fn reassign_upc(context: Context<ReassignUpcAccountConstraints>, reason_code: u8) -> Result<()> {
+        admin::identifiers::reassign_upc(context, reason_code)
+    }
+
+    pub fn reassign_fingerprint(
+        context: Context<ReassignFingerprintAccountConstraints>,
+        reason_code: u8,
+    ) -> Result<()> {
+        admin::identifiers::reassign_fingerprint(context, reason_code)
+    }
+
+    pub fn revoke_fingerprint(
+        context: Context<RevokeFingerprintAccountConstraints>,
+        reason_code: u8,
+    ) -> Result<()> {
+        admin::identifiers::revoke_fingerprint(context, reason_code)
+    }
+
+    pub fn reserve_artist_handle(
+        context: Context<ReserveArtistHandleAccountConstraints>,
+        name: String,
+    ) -> Result<()> {
+        admin::artist::reserve_artist_handle(context, name)
+    }
+
+    pub fn release_artist_handle_reservation(
+        context: Context<ReleaseArtistHandleReservationAccountConstraints>,
+    ) -> Result<()> {
+        admin::artist::release_artist_handle_reservation(context)
+    }
+
+    pub fn configure_admin_multisig(
+        context: Context<ConfigureAdminMultisigAccountConstraints>,
+        admins: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        admin::multisig::configure_admin_multisig(context, admins, threshold)
+    }
+
+    pub fn propose_admin_action(
+        context: Context<ProposeAdminActionAccountConstraints>,
+        action: AdminAction,
+    ) -> Result<()> {
+        admin::multisig::propose_admin_action(context, action)
+    }
+
+    pub fn approve_admin_proposal(
+        context: Context<ApproveAdminProposalAccountConstraints>,
+    ) -> Result<()> {
+        admin::multisig::approve_admin_proposal(context)
+    }
+
+    pub fn execute_admin_proposal(
+        context: Context<ExecuteAdminProposalAccountConstraints>,
+    ) -> Result<()> {
+        admin::multisig::execute_admin_proposal(context)
+    }
+
+    pub fn close_admin_proposal(
+        context: Context<CloseAdminProposalAccountConstraints>,
+    ) -> Result<()> {
+        admin::multisig::close_admin_proposal(context)
+    }
+
+    pub fn grant_role(context: Context<GrantRoleAccountConstraints>, role: Role) -> Result<()> {
+        admin::roles::grant_role(context, role)
+    }
+
+    pub fn revoke_role(context: Context<RevokeRoleAccountConstraints>) -> Result<()> {
+        admin::roles::revoke_role(context)
+    }
+
+    pub fn set_fee_override(
+        context: Context<SetFeeOverrideAccountConstraints>,
+        mint_fee: Option<u64>,
+        platform_fee_basis_points: Option<u16>,
+        note: String,
+    ) -> Result<()> {
+        admin::fees::set_fee_override(context, mint_fee, platform_fee_basis_points, note)
+    }
+
+    pub fn remove_fee_override(context: Context<RemoveFeeOverrideAccountConstraints>) -> Result<()> {
+        admin::fees::remove_fee_override(context)
+    }
+
+    pub fn issue_fee_voucher(
+        context: Context<IssueFeeVoucherAccountConstraints>,
+        uses: u16,
+        expires_at: i64,
+    ) -> Result<()> {
+        admin::fees::issue_fee_voucher(context, uses, expires_at)
+    }
+
+    pub fn revoke_fee_voucher(context: Context<RevokeFeeVoucherAccountConstraints>) -> Result<()> {
+        admin::fees::revoke_fee_voucher(context)
+    }
+
+    pub fn set_referral_fee(
+        context: Context<UpdateTreasuryConfigAccountConstraints>,
+        referral_fee_basis_points: u16,
+    ) -> Result<()> {
+        admin::fees::set_referral_fee(context, referral_fee_basis_points)
+    }
+
+    pub fn register_streaming_batch<'info>(
+        context: Context<'_, '_, '_, 'info, RegisterStreamingBatchAccountConstraints<'info>>,
         streaming_data: Vec<StreamingData>,
     ) -> Result<()> {
         streaming::register_streaming_batch(context, streaming_data)
     }
 
+    pub fn create_label(
+        context: Context<CreateLabelAccountConstraints>,
+        name: String,
+        royalty_wallet: Pubkey,
+        default_share_basis_points: u16,
+    ) -> Result<()> {
+        record_label::create_label(context, name, royalty_wallet, default_share_basis_points)
+    }
+
+    pub fn update_label(
+        context: Context<UpdateLabelAccountConstraints>,
+        name: Option<String>,
+        royalty_wallet: Option<Pubkey>,
+        default_share_basis_points: Option<u16>,
+        managers: Option<Vec<Pubkey>>,
+    ) -> Result<()> {
+        record_label::update_label(context, name, royalty_wallet, default_share_basis_points, managers)
+    }
+
+    pub fn invite_artist_to_label(context: Context<InviteArtistToLabelAccountConstraints>) -> Result<()> {
+        record_label::invite_artist_to_label(context)
+    }
+
+    pub fn accept_label_invite(context: Context<AcceptLabelInviteAccountConstraints>) -> Result<()> {
+        record_label::accept_label_invite(context)
+    }
+
+    pub fn end_label_membership(context: Context<EndLabelMembershipAccountConstraints>) -> Result<()> {
+        record_label::end_label_membership(context)
+    }
+
     pub fn create_collection(
         context: Context<CreateCollectionAccountConstraints>,
         name: String,
         description: String,
         uri: String,
+        upc: Option<String>,
     ) -> Result<()> {
-        collection::create_collection(context, name, description, uri)
+        collection::create_collection(context, name, description, uri, upc)
     }
     pub fn add_to_collection(context: Context<AddToCollectionAccountConstraints>) -> Result<()> {
         collection::add_to_collection(context)
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeCategory, Role, Treasury};
+
+#[account]
+pub struct AdminProposal {
+    pub treasury: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub created_at: i64,
+    pub executed_at: i64,
+    pub bump: u8,
+    pub expires_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AdminAction {
+    UpdateTreasuryConfig {
+        mint_fee: Option<u64>,
+        platform_fee_basis_points: Option<u16>,
+        verified_mint_fee: Option<u64>,
+        verified_platform_fee_basis_points: Option<u16>,
+        new_treasury_wallet: Option<Pubkey>,
+    },
+    UpdateStreamingProvider {
+        new_streaming_provider: Pubkey,
+    },
+    WithdrawTreasuryFunds {
+        category: FeeCategory,
+        amount: u64,
+    },
+    VerifyArtist {
+        artist_profile: Pubkey,
+        verify: bool,
+    },
+    SetAdmins {
+        admins: Vec<Pubkey>,
+        threshold: u8,
+    },
+    ProposeTreasuryAuthority {
+        new_authority: Pubkey,
+    },
+    CancelTreasuryAuthorityTransfer,
+    CancelTreasuryConfig,
+    SetGuardian {
+        new_guardian: Pubkey,
+    },
+    GrantRole {
+        holder: Pubkey,
+        role: Role,
+    },
+    RevokeRole {
+        holder: Pubkey,
+        role: Role,
+    },
+    SetReferralFee {
+        referral_fee_basis_points: u16,
+    },
+    SetFeeOverride {
+        artist_profile: Pubkey,
+        mint_fee: Option<u64>,
+        platform_fee_basis_points: Option<u16>,
+        note: String,
+    },
+    RemoveFeeOverride {
+        artist_profile: Pubkey,
+    },
+    IssueFeeVoucher {
+        artist_profile: Pubkey,
+        uses: u16,
+        expires_at: i64,
+    },
+}
+
+impl AdminProposal {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const BOOL_LENGTH: usize = 1;
+    pub const U64_LENGTH: usize = 8;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const ENUM_LENGTH: usize = 1;
+    pub const VECTOR_PREFIX_LENGTH: usize = 4;
+
+    // SetAdmins is the largest action variant
+    pub const MAX_ACTION_SIZE: usize =
+        Self::ENUM_LENGTH +
+        Self::VECTOR_PREFIX_LENGTH +
+        Treasury::MAX_ADMINS * Self::PUBKEY_LENGTH +  // admins
+        Self::U8_LENGTH;                               // threshold
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +                          // treasury
+        Self::U64_LENGTH +                             // index
+        Self::PUBKEY_LENGTH +                          // proposer
+        Self::MAX_ACTION_SIZE +                        // action
+        Self::VECTOR_PREFIX_LENGTH +                   // approvals vector prefix
+        Treasury::MAX_ADMINS * Self::PUBKEY_LENGTH +   // approvals
+        Self::BOOL_LENGTH +                            // executed
+        Self::I64_LENGTH +                             // created_at
+        Self::I64_LENGTH +                             // executed_at
+        Self::U8_LENGTH +                              // bump
+        Self::I64_LENGTH;                              // expires_at
+
+    /// Unexecuted proposals stop collecting approvals and can no longer run once they lapse
+    pub fn is_expired(&self, timestamp: i64) -> bool {
+        !self.executed && timestamp >= self.expires_at
+    }
+
+    /// Approvals only count while the approver is still part of the admin set
+    pub fn approval_count(&self, treasury: &Treasury) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approver| treasury.is_admin(approver))
+            .count()
+    }
+}
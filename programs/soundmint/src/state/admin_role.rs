@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::Treasury;
+
+#[account]
+pub struct AdminRole {
+    pub holder: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Verifier,
+    FeeManager,
+    StreamingManager,
+    Withdrawer,
+    Moderator,
+}
+
+impl Role {
+    pub fn seed(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+}
+
+impl AdminRole {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const ENUM_LENGTH: usize = 1;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // holder
+        Self::ENUM_LENGTH +       // role
+        Self::PUBKEY_LENGTH +     // granted_by
+        Self::I64_LENGTH +        // granted_at
+        Self::U8_LENGTH;          // bump
+
+    /// The treasury authority can perform every admin operation; role holders only their own.
+    /// Role accounts are seeded by holder and role, so callers only pass the signer's own role.
+    pub fn has_access(
+        treasury: &Treasury,
+        signer: &Pubkey,
+        role_account: Option<&AdminRole>,
+        role: Role,
+    ) -> bool {
+        treasury.authority == *signer
+            || role_account.is_some_and(|account| account.holder == *signer && account.role == role)
+    }
+}
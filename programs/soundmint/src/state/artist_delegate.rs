@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::state::{ArtistProfile, Label};
+
+#[account]
+pub struct ArtistDelegate {
+    pub artist_profile: Pubkey,
+    pub delegate: Pubkey,
+    pub permissions: u8, // Bitmask of DELEGATE_* flags
+    pub granted_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub generation: u32, // Profile delegate_generation the grant was made under
+}
+
+impl ArtistDelegate {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const U32_LENGTH: usize = 4;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // artist_profile
+        Self::PUBKEY_LENGTH +     // delegate
+        Self::U8_LENGTH +         // permissions
+        Self::I64_LENGTH +        // granted_at
+        Self::I64_LENGTH +        // updated_at
+        Self::U8_LENGTH +         // bump
+        Self::U32_LENGTH;         // generation
+
+    /// True if `signer` is the profile authority, a delegate holding `permission`,
+    /// or a manager of the label the artist belongs to
+    pub fn has_access(
+        artist_profile: &Account<ArtistProfile>,
+        signer: &Pubkey,
+        delegate_account: Option<&ArtistDelegate>,
+        label: Option<&Account<Label>>,
+        permission: u8,
+    ) -> bool {
+        artist_profile.authority == *signer
+            || delegate_account.is_some_and(|account| {
+                account.grants(
+                    &artist_profile.key(),
+                    artist_profile.delegate_generation,
+                    signer,
+                    permission,
+                )
+            })
+            || label.is_some_and(|label| {
+                artist_profile.label == Some(label.key()) && label.is_manager(signer)
+            })
+    }
+    /// Grants made before the profile last changed owners no longer carry any permission
+    pub fn grants(
+        &self,
+        artist_profile: &Pubkey,
+        delegate_generation: u32,
+        signer: &Pubkey,
+        permission: u8,
+    ) -> bool {
+        self.artist_profile == *artist_profile
+            && self.generation == delegate_generation
+            && self.delegate == *signer
+            && self.permissions & permission != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{DELEGATE_MINT_TRACKS, DELEGATE_UPDATE_METADATA};
+
+    #[test]
+    fn grants_lapse_when_the_profile_changes_owners() {
+        let (artist_profile, delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let artist_delegate = ArtistDelegate {
+            artist_profile,
+            delegate,
+            permissions: DELEGATE_MINT_TRACKS,
+            granted_at: 0,
+            updated_at: 0,
+            bump: 0,
+            generation: 1,
+        };
+
+        assert!(artist_delegate.grants(&artist_profile, 1, &delegate, DELEGATE_MINT_TRACKS));
+        assert!(!artist_delegate.grants(&artist_profile, 1, &delegate, DELEGATE_UPDATE_METADATA));
+        assert!(!artist_delegate.grants(&artist_profile, 2, &delegate, DELEGATE_MINT_TRACKS));
+        assert!(!artist_delegate.grants(&Pubkey::new_unique(), 1, &delegate, DELEGATE_MINT_TRACKS));
+    }
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::error::CustomError;
+
+#[account]
+pub struct ArtistHandle {
+    pub handle: String,               // Normalized lowercase handle
+    pub artist_profile: Pubkey,       // Profile holding the handle, default while unclaimed
+    pub reserved_for: Option<Pubkey>, // Profile allowed to claim a protected handle
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl ArtistHandle {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
+    pub const STRING_PREFIX_LENGTH: usize = 4;
+
+    pub const MAX_HANDLE_LENGTH: usize = 50;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::STRING_PREFIX_LENGTH + Self::MAX_HANDLE_LENGTH + // handle
+        Self::PUBKEY_LENGTH +                                  // artist_profile
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH +     // reserved_for
+        Self::I64_LENGTH +                                     // created_at
+        Self::I64_LENGTH +                                     // updated_at
+        Self::U8_LENGTH;                                       // bump
+
+    /// Lowercases `name` and strips everything but ASCII letters and digits,
+    /// so "Cosmic Rhythms" and "cosmic-rhythms" map to the same handle
+    pub fn normalize(name: &str) -> String {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|character| character.to_ascii_lowercase())
+            .collect()
+    }
+
+    /// PDA seed for a name; hashed because a handle can exceed the 32 byte seed limit
+    pub fn seed_hash(name: &str) -> [u8; 32] {
+        hash(Self::normalize(name).as_bytes()).to_bytes()
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.artist_profile != Pubkey::default()
+    }
+
+    /// Assigns the handle to `artist_profile`, initializing it on first use
+    pub fn claim(
+        &mut self,
+        name: &str,
+        artist_profile: Pubkey,
+        bump: u8,
+        timestamp: i64,
+    ) -> Result<()> {
+        let handle = Self::normalize(name);
+        require!(!handle.is_empty(), CustomError::InvalidHandle);
+        require!(
+            !self.is_claimed() || self.artist_profile == artist_profile,
+            CustomError::HandleTaken
+        );
+        if let Some(reserved_for) = self.reserved_for {
+            require!(reserved_for == artist_profile, CustomError::HandleReserved);
+        }
+
+        if self.handle.is_empty() {
+            self.handle = handle;
+            self.reserved_for = None;
+            self.created_at = timestamp;
+            self.bump = bump;
+        }
+        self.artist_profile = artist_profile;
+        self.updated_at = timestamp;
+        Ok(())
+    }
+
+    /// Lets go of the handle and returns whether its account can be closed.
+    /// Reserved handles stay behind for their designated profile.
+    pub fn release(&mut self, timestamp: i64) -> bool {
+        self.artist_profile = Pubkey::default();
+        self.updated_at = timestamp;
+        self.reserved_for.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unclaimed_handle() -> ArtistHandle {
+        ArtistHandle {
+            handle: String::new(),
+            artist_profile: Pubkey::default(),
+            reserved_for: None,
+            created_at: 0,
+            updated_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn normalize_keeps_only_lowercase_letters_and_digits() {
+        assert_eq!(ArtistHandle::normalize("Cosmic Rhythms"), "cosmicrhythms");
+        assert_eq!(ArtistHandle::normalize("cosmic-rhythms_2024!"), "cosmicrhythms2024");
+        assert_eq!(ArtistHandle::normalize("Björk"), "bjrk");
+        assert_eq!(ArtistHandle::normalize(" -_ "), "");
+    }
+
+    #[test]
+    fn seed_hash_matches_for_names_with_the_same_handle() {
+        assert_eq!(ArtistHandle::seed_hash("Cosmic Rhythms"), ArtistHandle::seed_hash("COSMIC-RHYTHMS"));
+        assert_ne!(ArtistHandle::seed_hash("Cosmic Rhythms"), ArtistHandle::seed_hash("Cosmic Rhythm"));
+    }
+
+    #[test]
+    fn claim_rejects_a_handle_held_by_another_profile() {
+        let (artist_profile, other_profile) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut artist_handle = unclaimed_handle();
+
+        artist_handle.claim("Cosmic Rhythms", artist_profile, 254, 1).unwrap();
+        assert_eq!(artist_handle.handle, "cosmicrhythms");
+        assert!(artist_handle.claim("cosmic rhythms", artist_profile, 254, 2).is_ok());
+        assert_eq!(
+            artist_handle.claim("cosmic rhythms", other_profile, 254, 3).unwrap_err(),
+            CustomError::HandleTaken.into()
+        );
+    }
+
+    #[test]
+    fn reserved_handle_can_only_be_claimed_by_its_profile() {
+        let (verified_profile, other_profile) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut artist_handle = unclaimed_handle();
+        artist_handle.handle = "cosmicrhythms".to_string();
+        artist_handle.reserved_for = Some(verified_profile);
+
+        assert_eq!(
+            artist_handle.claim("Cosmic Rhythms", other_profile, 254, 1).unwrap_err(),
+            CustomError::HandleReserved.into()
+        );
+        artist_handle.claim("Cosmic Rhythms", verified_profile, 254, 1).unwrap();
+
+        // Releasing keeps the reservation in place instead of closing the account
+        assert!(!artist_handle.release(2));
+        assert!(!artist_handle.is_claimed());
+        assert_eq!(artist_handle.reserved_for, Some(verified_profile));
+    }
+}
@@ -12,6 +12,21 @@ pub struct ArtistProfile {
     pub created_at: i64,
     pub updated_at: i64,
     pub bump: u8,
+    pub creator: Pubkey, // Wallet the profile PDA is seeded by, fixed across authority transfers
+    pub pending_authority: Option<Pubkey>,
+    pub verification_tier: VerificationTier,
+    pub verified_at: i64,
+    pub referrer: Option<Pubkey>,
+    pub label: Option<Pubkey>,
+    pub delegate_generation: u32, // Bumped on every ownership transfer to retire earlier delegates
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationTier {
+    Unverified,
+    Standard,
+    Notable,
+    Official,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -27,6 +42,9 @@ impl ArtistProfile {
     pub const U64_LENGTH: usize = 8;
     pub const I64_LENGTH: usize = 8;
     pub const U8_LENGTH: usize = 1;
+    pub const U32_LENGTH: usize = 4;
+    pub const ENUM_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
     pub const STRING_PREFIX_LENGTH: usize = 4; // Length prefix for strings
     pub const VECTOR_PREFIX_LENGTH: usize = 4; // Length prefix for vectors
 
@@ -50,5 +68,12 @@ impl ArtistProfile {
         Self::U64_LENGTH +                      // track_count
         Self::I64_LENGTH +                      // created_at
         Self::I64_LENGTH +                      // updated_at
-        Self::U8_LENGTH;                        // bump
+        Self::U8_LENGTH +                       // bump
+        Self::PUBKEY_LENGTH +                   // creator
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH + // pending_authority
+        Self::ENUM_LENGTH +                     // verification_tier
+        Self::I64_LENGTH +                      // verified_at
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH + // referrer
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH + // label
+        Self::U32_LENGTH;                       // delegate_generation
 }
\ No newline at end of file
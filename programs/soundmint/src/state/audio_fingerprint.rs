@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct AudioFingerprint {
+    pub digest: [u8; 32],       // Perceptual fingerprint digest computed off-chain
+    pub original: Pubkey,       // First master NFT minted with this recording
+    pub artist_profile: Pubkey, // Only this artist may register further versions
+    pub version_count: u16,
+    pub registered_at: i64,
+    pub bump: u8,
+    pub reassigned_by: Option<Pubkey>, // Moderator who last reassigned or revoked the recording
+    pub reason_code: u8,
+    pub updated_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VersionKind {
+    Remaster,
+    RadioEdit,
+    ExtendedMix,
+    Instrumental,
+    Live,
+}
+
+/// Link from a master NFT to the original recording it shares a fingerprint with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TrackVersion {
+    pub original: Pubkey,
+    pub kind: VersionKind,
+}
+
+impl TrackVersion {
+    pub const SIZE: usize = AudioFingerprint::PUBKEY_LENGTH + AudioFingerprint::ENUM_LENGTH; // original + kind
+}
+
+impl AudioFingerprint {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const HASH_LENGTH: usize = 32;
+    pub const U16_LENGTH: usize = 2;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const ENUM_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::HASH_LENGTH +       // digest
+        Self::PUBKEY_LENGTH +     // original
+        Self::PUBKEY_LENGTH +     // artist_profile
+        Self::U16_LENGTH +        // version_count
+        Self::I64_LENGTH +        // registered_at
+        Self::U8_LENGTH +         // bump
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH + // reassigned_by
+        Self::U8_LENGTH +         // reason_code
+        Self::I64_LENGTH;         // updated_at
+
+    pub fn is_registered(&self) -> bool {
+        self.original != Pubkey::default()
+    }
+
+    /// Once the original is gone, only the artist behind the remaining versions may register
+    /// a new one. A revoked recording is open to anyone.
+    pub fn can_register_original(&self, artist_profile: &Pubkey) -> bool {
+        !self.is_registered()
+            && (self.version_count == 0
+                || self.artist_profile == Pubkey::default()
+                || self.artist_profile == *artist_profile)
+    }
+
+    /// The entry is only closed when neither the original nor any version still points at it
+    pub fn is_released(&self) -> bool {
+        !self.is_registered() && self.version_count == 0
+    }
+
+    /// Moves the original to a new track after a moderator resolves a dispute
+    pub fn reassign(
+        &mut self,
+        original: Pubkey,
+        artist_profile: Pubkey,
+        moderator: Pubkey,
+        reason_code: u8,
+        timestamp: i64,
+    ) {
+        self.original = original;
+        self.artist_profile = artist_profile;
+        self.reassigned_by = Some(moderator);
+        self.reason_code = reason_code;
+        self.updated_at = timestamp;
+    }
+
+    /// Drops a track's claim on the recording. Revoking the original also drops the artist's
+    /// hold, so the rightful owner can mint it.
+    pub fn revoke(&mut self, master_nft: &Pubkey, moderator: Pubkey, reason_code: u8, timestamp: i64) {
+        if *master_nft == self.original {
+            self.original = Pubkey::default();
+            self.artist_profile = Pubkey::default();
+        } else {
+            self.version_count = self.version_count.checked_sub(1).unwrap();
+        }
+        self.reassigned_by = Some(moderator);
+        self.reason_code = reason_code;
+        self.updated_at = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registered(version_count: u16) -> AudioFingerprint {
+        AudioFingerprint {
+            digest: [7; 32],
+            original: Pubkey::new_unique(),
+            artist_profile: Pubkey::new_unique(),
+            version_count,
+            registered_at: 0,
+            bump: 0,
+            reassigned_by: None,
+            reason_code: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn released_once_original_and_versions_are_gone() {
+        let mut audio_fingerprint = registered(1);
+        assert!(!audio_fingerprint.is_released());
+
+        audio_fingerprint.original = Pubkey::default();
+        assert!(!audio_fingerprint.is_released());
+
+        audio_fingerprint.version_count = 0;
+        assert!(audio_fingerprint.is_released());
+    }
+
+    #[test]
+    fn remaining_versions_hold_the_recording_for_their_artist() {
+        let mut audio_fingerprint = registered(1);
+        let artist_profile = audio_fingerprint.artist_profile;
+        assert!(!audio_fingerprint.can_register_original(&artist_profile));
+
+        audio_fingerprint.original = Pubkey::default();
+        assert!(audio_fingerprint.can_register_original(&artist_profile));
+        assert!(!audio_fingerprint.can_register_original(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn revoking_the_original_opens_the_recording() {
+        let mut audio_fingerprint = registered(1);
+        let original = audio_fingerprint.original;
+        let moderator = Pubkey::new_unique();
+
+        audio_fingerprint.revoke(&Pubkey::new_unique(), moderator, 3, 10);
+        assert_eq!(audio_fingerprint.version_count, 0);
+        assert!(audio_fingerprint.is_registered());
+
+        audio_fingerprint.revoke(&original, moderator, 3, 10);
+        assert!(audio_fingerprint.is_released());
+        assert!(audio_fingerprint.can_register_original(&Pubkey::new_unique()));
+        assert_eq!(audio_fingerprint.reassigned_by, Some(moderator));
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct DerivativeLink {
+    pub derivative: Pubkey,          // Master NFT built on the parent
+    pub parent: Pubkey,              // Master NFT being remixed, sampled or covered
+    pub kind: DerivativeKind,
+    pub upstream_basis_points: u16,  // Share of the derivative's revenue owed to the parent
+    pub status: DerivativeLinkStatus,
+    pub declared_by: Pubkey,         // Paid the rent, refunded if the parent declines
+    pub declared_at: i64,
+    pub accepted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DerivativeKind {
+    Remix,
+    Sample,
+    Cover,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DerivativeLinkStatus {
+    Pending,
+    Accepted,
+}
+
+impl DerivativeLink {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const ENUM_LENGTH: usize = 1;
+    pub const U16_LENGTH: usize = 2;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // derivative
+        Self::PUBKEY_LENGTH +     // parent
+        Self::ENUM_LENGTH +       // kind
+        Self::U16_LENGTH +        // upstream_basis_points
+        Self::ENUM_LENGTH +       // status
+        Self::PUBKEY_LENGTH +     // declared_by
+        Self::I64_LENGTH +        // declared_at
+        Self::I64_LENGTH +        // accepted_at
+        Self::U8_LENGTH;          // bump
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct FeeOverride {
+    pub artist_profile: Pubkey,
+    pub mint_fee: Option<u64>,
+    pub platform_fee_basis_points: Option<u16>,
+    pub note: String,
+    pub set_by: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub pending_change: Option<PendingFeeOverride>,
+}
+
+// Override values that take effect once the fee timelock has passed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingFeeOverride {
+    pub mint_fee: Option<u64>,
+    pub platform_fee_basis_points: Option<u16>,
+    pub effective_at: i64,
+}
+
+impl FeeOverride {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const U16_LENGTH: usize = 2;
+    pub const U64_LENGTH: usize = 8;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
+    pub const STRING_PREFIX_LENGTH: usize = 4;
+
+    pub const MAX_NOTE_LENGTH: usize = 50;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +                              // artist_profile
+        Self::OPTION_PREFIX_LENGTH + Self::U64_LENGTH +    // mint_fee
+        Self::OPTION_PREFIX_LENGTH + Self::U16_LENGTH +    // platform_fee_basis_points
+        Self::STRING_PREFIX_LENGTH + Self::MAX_NOTE_LENGTH + // note
+        Self::PUBKEY_LENGTH +                              // set_by
+        Self::I64_LENGTH +                                 // updated_at
+        Self::U8_LENGTH +                                  // bump
+        Self::OPTION_PREFIX_LENGTH +                       // pending_change
+        Self::OPTION_PREFIX_LENGTH + Self::U64_LENGTH +    // pending mint_fee
+        Self::OPTION_PREFIX_LENGTH + Self::U16_LENGTH +    // pending platform_fee_basis_points
+        Self::I64_LENGTH;                                  // pending effective_at
+
+    /// Reads the account at an artist's override address; an uninitialized
+    /// account means the artist has no override
+    pub fn load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.owner == &crate::ID && !account.data_is_empty() {
+            Ok(Some(Self::try_deserialize(&mut &account.try_borrow_data()?[..])?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Override values in force at `now`, including a matured pending change
+    pub fn effective_fees(&self, now: i64) -> (Option<u64>, Option<u16>) {
+        match &self.pending_change {
+            Some(pending) if pending.effective_at <= now => {
+                (pending.mint_fee, pending.platform_fee_basis_points)
+            }
+            _ => (self.mint_fee, self.platform_fee_basis_points),
+        }
+    }
+
+    /// Applies a scheduled change once its timelock has passed.
+    /// Returns true if the override was modified.
+    pub fn apply_pending_change(&mut self, now: i64) -> bool {
+        let pending = match &self.pending_change {
+            Some(pending) if pending.effective_at <= now => pending.clone(),
+            _ => return false,
+        };
+
+        self.mint_fee = pending.mint_fee;
+        self.platform_fee_basis_points = pending.platform_fee_basis_points;
+        self.pending_change = None;
+        self.updated_at = now;
+        true
+    }
+
+    /// True while the override leaves every fee at the treasury schedule
+    pub fn is_neutral(&self) -> bool {
+        self.mint_fee.is_none() && self.platform_fee_basis_points.is_none()
+    }
+}
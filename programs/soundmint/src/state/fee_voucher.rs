@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct FeeVoucher {
+    pub beneficiary: Pubkey, // Artist profile the voucher is issued to
+    pub issuer: Pubkey,
+    pub remaining_uses: u16,
+    pub redeemed_count: u16,
+    pub expires_at: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl FeeVoucher {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const U16_LENGTH: usize = 2;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+
+    // A single issuance can waive at most this many mints, for at most this long
+    pub const MAX_USES: u16 = 25;
+    pub const MAX_VALIDITY_SECONDS: i64 = 90 * 24 * 60 * 60;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // beneficiary
+        Self::PUBKEY_LENGTH +     // issuer
+        Self::U16_LENGTH +        // remaining_uses
+        Self::U16_LENGTH +        // redeemed_count
+        Self::I64_LENGTH +        // expires_at
+        Self::I64_LENGTH +        // created_at
+        Self::U8_LENGTH;          // bump
+
+    /// Reads the account at an artist's voucher address; an uninitialized
+    /// account means no voucher has been issued
+    pub fn load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.owner == &crate::ID && !account.data_is_empty() {
+            Ok(Some(Self::try_deserialize(&mut &account.try_borrow_data()?[..])?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn is_redeemable(&self, now: i64) -> bool {
+        self.remaining_uses > 0 && now < self.expires_at
+    }
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+#[account]
+pub struct IdentifierRegistration {
+    pub kind: IdentifierKind,
+    pub code: String,
+    pub holder: Pubkey,                // Master NFT for an ISRC, collection for a UPC
+    pub artist_profile: Pubkey,
+    pub reassigned_by: Option<Pubkey>, // Admin who last resolved a dispute over the code
+    pub reason_code: u8,
+    pub registered_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IdentifierKind {
+    Isrc,
+    Upc,
+}
+
+impl IdentifierKind {
+    pub fn seed(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+}
+
+impl IdentifierRegistration {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const ENUM_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
+    pub const STRING_PREFIX_LENGTH: usize = 4;
+
+    pub const ISRC_LENGTH: usize = 12;
+    pub const UPC_A_LENGTH: usize = 12;
+    pub const UPC_LENGTH: usize = 13; // EAN-13 form of a UPC
+    pub const MAX_CODE_LENGTH: usize = Self::UPC_LENGTH;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::ENUM_LENGTH +                                   // kind
+        Self::STRING_PREFIX_LENGTH + Self::MAX_CODE_LENGTH +  // code
+        Self::PUBKEY_LENGTH +                                 // holder
+        Self::PUBKEY_LENGTH +                                 // artist_profile
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH +    // reassigned_by
+        Self::U8_LENGTH +                                     // reason_code
+        Self::I64_LENGTH +                                    // registered_at
+        Self::I64_LENGTH +                                    // updated_at
+        Self::U8_LENGTH;                                      // bump
+
+    /// Registration PDAs are seeded by a hash of the code, so a malformed code of any length
+    /// still derives an address and is rejected by validation rather than by the seed limit
+    pub fn code_seed(code: &str) -> [u8; 32] {
+        hash(code.as_bytes()).to_bytes()
+    }
+
+    /// A UPC-A is an EAN-13 with a leading zero, so both spellings of a barcode share one
+    /// registration. Anything else is returned unchanged for validation to reject.
+    pub fn canonical_upc(code: &str) -> String {
+        if code.len() == Self::UPC_A_LENGTH && code.bytes().all(|byte| byte.is_ascii_digit()) {
+            format!("0{}", code)
+        } else {
+            code.to_string()
+        }
+    }
+
+    /// Codes are stored in canonical form: uppercase, no hyphens, and UPCs as EAN-13
+    pub fn is_valid_code(kind: IdentifierKind, code: &str) -> bool {
+        match kind {
+            IdentifierKind::Isrc => Self::is_valid_isrc(code),
+            IdentifierKind::Upc => Self::is_valid_upc(code),
+        }
+    }
+
+    /// Country code, registrant code, year of reference and designation code
+    fn is_valid_isrc(code: &str) -> bool {
+        let bytes = code.as_bytes();
+        bytes.len() == Self::ISRC_LENGTH
+            && bytes[..2].iter().all(u8::is_ascii_uppercase)
+            && bytes[2..5].iter().all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit())
+            && bytes[5..].iter().all(u8::is_ascii_digit)
+    }
+
+    /// EAN-13 with a valid GS1 check digit
+    fn is_valid_upc(code: &str) -> bool {
+        let bytes = code.as_bytes();
+        if bytes.len() != Self::UPC_LENGTH || !bytes.iter().all(u8::is_ascii_digit) {
+            return false;
+        }
+
+        let (body, check_digit) = bytes.split_at(bytes.len() - 1);
+        let sum: u32 = body
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, byte)| {
+                let digit = (byte - b'0') as u32;
+                if index % 2 == 0 { digit * 3 } else { digit }
+            })
+            .sum();
+        (10 - sum % 10) % 10 == (check_digit[0] - b'0') as u32
+    }
+
+    pub fn register(
+        &mut self,
+        kind: IdentifierKind,
+        code: String,
+        holder: Pubkey,
+        artist_profile: Pubkey,
+        bump: u8,
+        timestamp: i64,
+    ) {
+        self.kind = kind;
+        self.code = code;
+        self.holder = holder;
+        self.artist_profile = artist_profile;
+        self.reassigned_by = None;
+        self.reason_code = 0;
+        self.registered_at = timestamp;
+        self.updated_at = timestamp;
+        self.bump = bump;
+    }
+
+    /// Moves the code to a new holder after an admin resolves a dispute
+    pub fn reassign(
+        &mut self,
+        holder: Pubkey,
+        artist_profile: Pubkey,
+        moderator: Pubkey,
+        reason_code: u8,
+        timestamp: i64,
+    ) {
+        self.holder = holder;
+        self.artist_profile = artist_profile;
+        self.reassigned_by = Some(moderator);
+        self.reason_code = reason_code;
+        self.updated_at = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_valid_isrc_checks_each_segment() {
+        assert!(IdentifierRegistration::is_valid_isrc("USS1Z9900001"));
+        assert!(IdentifierRegistration::is_valid_isrc("GBAYE0601498"));
+        assert!(!IdentifierRegistration::is_valid_isrc("US-S1Z-99-00001"));
+        assert!(!IdentifierRegistration::is_valid_isrc("usS1Z9900001"));
+        assert!(!IdentifierRegistration::is_valid_isrc("U1S1Z9900001"));
+        assert!(!IdentifierRegistration::is_valid_isrc("USS1Z99000A1"));
+        assert!(!IdentifierRegistration::is_valid_isrc("USS1Z990001"));
+    }
+
+    #[test]
+    fn is_valid_upc_checks_the_check_digit() {
+        assert!(IdentifierRegistration::is_valid_upc("4006381333931"));
+        assert!(IdentifierRegistration::is_valid_upc("0036000291452"));
+        assert!(!IdentifierRegistration::is_valid_upc("4006381333932"));
+        assert!(!IdentifierRegistration::is_valid_upc("400638133393A"));
+        // UPC-A has to be canonicalised first
+        assert!(!IdentifierRegistration::is_valid_upc("036000291452"));
+    }
+
+    #[test]
+    fn canonical_upc_pads_upc_a_to_ean_13() {
+        assert_eq!(IdentifierRegistration::canonical_upc("036000291452"), "0036000291452");
+        assert_eq!(IdentifierRegistration::canonical_upc("0036000291452"), "0036000291452");
+        assert_eq!(IdentifierRegistration::canonical_upc("03600029145A"), "03600029145A");
+        assert!(IdentifierRegistration::is_valid_code(
+            IdentifierKind::Upc,
+            &IdentifierRegistration::canonical_upc("036000291452")
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Label {
+    pub authority: Pubkey,
+    pub name: String,
+    pub royalty_wallet: Pubkey,           // Receives the label's share of new royalty splits
+    pub default_share_basis_points: u16,  // Injected into splits created by roster artists
+    pub managers: Vec<Pubkey>,
+    pub artist_count: u32,
+    pub total_revenue: u64,               // Revenue tracked across all roster tracks
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct LabelMembership {
+    pub label: Pubkey,
+    pub artist_profile: Pubkey,
+    pub status: MembershipStatus,
+    pub invited_by: Pubkey,
+    pub invited_at: i64,
+    pub joined_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MembershipStatus {
+    Invited,
+    Active,
+}
+
+impl Label {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const U16_LENGTH: usize = 2;
+    pub const U32_LENGTH: usize = 4;
+    pub const U64_LENGTH: usize = 8;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const STRING_PREFIX_LENGTH: usize = 4;
+    pub const VECTOR_PREFIX_LENGTH: usize = 4;
+
+    pub const MAX_NAME_LENGTH: usize = 50;
+    pub const MAX_MANAGERS: usize = 5;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +                               // authority
+        Self::STRING_PREFIX_LENGTH + Self::MAX_NAME_LENGTH + // name
+        Self::PUBKEY_LENGTH +                               // royalty_wallet
+        Self::U16_LENGTH +                                  // default_share_basis_points
+        Self::VECTOR_PREFIX_LENGTH +                        // managers vector prefix
+        Self::MAX_MANAGERS * Self::PUBKEY_LENGTH +          // managers
+        Self::U32_LENGTH +                                  // artist_count
+        Self::U64_LENGTH +                                  // total_revenue
+        Self::I64_LENGTH +                                  // created_at
+        Self::I64_LENGTH +                                  // updated_at
+        Self::U8_LENGTH;                                    // bump
+
+    pub fn is_manager(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.managers.contains(key)
+    }
+
+    pub fn record_revenue(&mut self, amount: u64) {
+        self.total_revenue = self.total_revenue.checked_add(amount).unwrap();
+    }
+}
+
+impl LabelMembership {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const ENUM_LENGTH: usize = 1;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // label
+        Self::PUBKEY_LENGTH +     // artist_profile
+        Self::ENUM_LENGTH +       // status
+        Self::PUBKEY_LENGTH +     // invited_by
+        Self::I64_LENGTH +        // invited_at
+        Self::I64_LENGTH +        // joined_at
+        Self::U8_LENGTH;          // bump
+}
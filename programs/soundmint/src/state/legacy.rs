@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    ArtistProfile,
+    AudioCodec,
+    AudioSpec,
+    FeeLedger,
+    MasterNft,
+    MasterNftStatus,
+    MetadataItem,
+    SocialLink,
+    Treasury,
+    VerificationTier,
+};
+
+// Layouts of accounts written by the first release. The current layouts only append fields
+// to these, and the migrations read an account with them and rewrite it in full.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyTreasury {
+    pub authority: Pubkey,
+    pub treasury_wallet: Pubkey,
+    pub streaming_provider: Pubkey,
+    pub mint_fee: u64,
+    pub platform_fee_basis_points: u16,
+    pub total_revenue_collected: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyArtistProfile {
+    pub authority: Pubkey,
+    pub name: String,
+    pub description: String,
+    pub profile_image_uri: String,
+    pub social_links: Vec<SocialLink>,
+    pub is_verified: bool,
+    pub track_count: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyMasterNft {
+    pub title: String,
+    pub description: String,
+    pub artist_profile: Pubkey,
+    pub audio_uri: String,
+    pub artwork_uri: String,
+    pub metadata: Vec<MetadataItem>,
+    pub mint: Pubkey,
+    pub is_transferable: bool,
+    pub status: MasterNftStatus,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyTreasury {
+    /// Verified artists pay the regular fees and the authority stays guardian until changed
+    pub fn upgrade(self) -> Treasury {
+        Treasury {
+            authority: self.authority,
+            treasury_wallet: self.treasury_wallet,
+            streaming_provider: self.streaming_provider,
+            mint_fee: self.mint_fee,
+            platform_fee_basis_points: self.platform_fee_basis_points,
+            total_revenue_collected: self.total_revenue_collected,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            bump: self.bump,
+            pending_authority: None,
+            guardian: self.authority,
+            paused_flags: 0,
+            verified_mint_fee: self.mint_fee,
+            verified_platform_fee_basis_points: self.platform_fee_basis_points,
+            referral_fee_basis_points: 0,
+            pending_config: None,
+            total_referral_rewards_paid: 0,
+            mint_fees: FeeLedger::default(),
+            claim_fees: FeeLedger::default(),
+            streaming_fees: FeeLedger::default(),
+            admins: Vec::new(),
+            admin_threshold: 0,
+            proposal_count: 0,
+        }
+    }
+}
+
+impl LegacyArtistProfile {
+    /// Profiles were seeded by their authority before they could change hands, so it becomes
+    /// the creator; verified profiles keep the base tier
+    pub fn upgrade(self) -> ArtistProfile {
+        let (verification_tier, verified_at) = if self.is_verified {
+            (VerificationTier::Standard, self.updated_at)
+        } else {
+            (VerificationTier::Unverified, 0)
+        };
+
+        ArtistProfile {
+            authority: self.authority,
+            name: self.name,
+            description: self.description,
+            profile_image_uri: self.profile_image_uri,
+            social_links: self.social_links,
+            is_verified: self.is_verified,
+            track_count: self.track_count,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            bump: self.bump,
+            creator: self.authority,
+            pending_authority: None,
+            verification_tier,
+            verified_at,
+            referrer: None,
+            label: None,
+            delegate_generation: 0,
+        }
+    }
+}
+
+impl LegacyMasterNft {
+    /// Content hashes, the fingerprint and the ISRC were not recorded yet and stay unset
+    pub fn upgrade(self) -> MasterNft {
+        MasterNft {
+            title: self.title,
+            description: self.description,
+            artist_profile: self.artist_profile,
+            audio_uri: self.audio_uri,
+            artwork_uri: self.artwork_uri,
+            metadata: self.metadata,
+            mint: self.mint,
+            is_transferable: self.is_transferable,
+            status: self.status,
+            created_at: self.created_at,
+            bump: self.bump,
+            audio_hash: [0; 32],
+            artwork_hash: [0; 32],
+            audio_spec: AudioSpec {
+                duration_seconds: 0,
+                codec: AudioCodec::Mp3,
+                sample_rate_hz: 0,
+            },
+            isrc: None,
+            fingerprint: [0; 32],
+            version: None,
+            metadata_uri: String::new(),
+            moderation_reason: 0,
+            moderated_at: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Data past the serialized fields is whatever a longer earlier write left behind
+    fn legacy_bytes<T: AnchorSerialize>(legacy: &T) -> Vec<u8> {
+        let mut data = legacy.try_to_vec().unwrap();
+        data.extend_from_slice(&[0xAB; 64]);
+        data
+    }
+
+    #[test]
+    fn current_layouts_extend_the_legacy_ones() {
+        let treasury = LegacyTreasury {
+            authority: Pubkey::new_unique(),
+            treasury_wallet: Pubkey::new_unique(),
+            streaming_provider: Pubkey::new_unique(),
+            mint_fee: 10_000_000,
+            platform_fee_basis_points: 500,
+            total_revenue_collected: 42,
+            created_at: 1,
+            updated_at: 2,
+            bump: 254,
+        }
+        .upgrade();
+        let legacy = LegacyTreasury::deserialize(&mut &treasury.try_to_vec().unwrap()[..]).unwrap();
+        assert_eq!(legacy.authority, treasury.authority);
+        assert_eq!(legacy.total_revenue_collected, 42);
+        assert_eq!(legacy.bump, 254);
+
+        let master_nft = LegacyMasterNft {
+            title: "Track".to_string(),
+            description: String::new(),
+            artist_profile: Pubkey::new_unique(),
+            audio_uri: String::new(),
+            artwork_uri: String::new(),
+            metadata: Vec::new(),
+            mint: Pubkey::new_unique(),
+            is_transferable: true,
+            status: MasterNftStatus::Delisted,
+            created_at: 1,
+            bump: 253,
+        }
+        .upgrade();
+        let legacy = LegacyMasterNft::deserialize(&mut &master_nft.try_to_vec().unwrap()[..]).unwrap();
+        assert_eq!(legacy.mint, master_nft.mint);
+        assert_eq!(legacy.status, MasterNftStatus::Delisted);
+        assert_eq!(legacy.bump, 253);
+    }
+
+    #[test]
+    fn legacy_profiles_upgrade_with_their_authority_as_creator() {
+        let authority = Pubkey::new_unique();
+        let data = legacy_bytes(&LegacyArtistProfile {
+            authority,
+            name: "Cosmic Rhythms".to_string(),
+            description: String::new(),
+            profile_image_uri: String::new(),
+            social_links: Vec::new(),
+            is_verified: true,
+            track_count: 3,
+            created_at: 1,
+            updated_at: 2,
+            bump: 255,
+        });
+
+        let artist_profile = LegacyArtistProfile::deserialize(&mut &data[..]).unwrap().upgrade();
+        assert_eq!(artist_profile.creator, authority);
+        assert_eq!(artist_profile.track_count, 3);
+        assert_eq!(artist_profile.verification_tier, VerificationTier::Standard);
+        assert_eq!(artist_profile.verified_at, 2);
+        assert!(artist_profile.pending_authority.is_none());
+        assert!(artist_profile.label.is_none());
+        assert_eq!(artist_profile.delegate_generation, 0);
+
+        let legacy = LegacyArtistProfile::deserialize(&mut &artist_profile.try_to_vec().unwrap()[..]).unwrap();
+        assert_eq!(legacy.name, "Cosmic Rhythms");
+        assert_eq!(legacy.bump, 255);
+    }
+}
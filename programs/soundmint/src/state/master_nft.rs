@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::metadata::mpl_token_metadata;
+use crate::state::{IdentifierRegistration, TrackVersion};
 
 #[account]
 pub struct MasterNft {
@@ -13,13 +16,95 @@ pub struct MasterNft {
     pub status: MasterNftStatus,
     pub created_at: i64,
     pub bump: u8,
+    pub audio_hash: [u8; 32],   // SHA-256 of the file behind audio_uri
+    pub artwork_hash: [u8; 32], // SHA-256 of the file behind artwork_uri
+    pub audio_spec: AudioSpec,
+    pub isrc: Option<String>,   // Backed by a unique identifier registration
+    pub fingerprint: [u8; 32],  // Perceptual digest backed by the fingerprint registry
+    pub version: Option<TrackVersion>,
+    pub metadata_uri: String, // Off-chain JSON referenced by the token metadata account
+    pub moderation_reason: u8, // Reason code from the last moderation decision
+    pub moderated_at: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MasterNftStatus {
     Active,
     Delisted,
-    Frozen,
+    Frozen,    // Set by moderators only
+    Draft,
+    TakenDown, // Set by moderators only
+}
+
+impl MasterNftStatus {
+    pub fn is_moderated(&self) -> bool {
+        matches!(self, MasterNftStatus::Frozen | MasterNftStatus::TakenDown)
+    }
+
+    /// Artists publish a draft once and can then delist and relist it freely
+    pub fn can_artist_transition(&self, to: MasterNftStatus) -> bool {
+        if *self == to {
+            return !self.is_moderated();
+        }
+        matches!(
+            (self, to),
+            (MasterNftStatus::Draft, MasterNftStatus::Active)
+                | (MasterNftStatus::Active, MasterNftStatus::Delisted)
+                | (MasterNftStatus::Delisted, MasterNftStatus::Active)
+        )
+    }
+
+    /// Moderators can lock any track, and lift a lock back to a published status
+    pub fn can_moderator_transition(&self, to: MasterNftStatus) -> bool {
+        match to {
+            MasterNftStatus::Frozen | MasterNftStatus::TakenDown => *self != to,
+            MasterNftStatus::Active | MasterNftStatus::Delisted => self.is_moderated(),
+            MasterNftStatus::Draft => false,
+        }
+    }
+
+    /// Delisted tracks keep earning from existing listeners; drafts and locked tracks do not
+    pub fn accepts_revenue(&self) -> bool {
+        matches!(self, MasterNftStatus::Active | MasterNftStatus::Delisted)
+    }
+
+    /// Payouts are held while a track is under moderation
+    pub fn allows_claims(&self) -> bool {
+        !self.is_moderated()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AudioCodec {
+    Mp3,
+    Aac,
+    Ogg,
+    Opus,
+    Flac,
+    Alac,
+    Wav,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AudioSpec {
+    pub duration_seconds: u32,
+    pub codec: AudioCodec,
+    pub sample_rate_hz: u32,
+}
+
+impl AudioSpec {
+    pub const SIZE: usize = MasterNft::U32_LENGTH + MasterNft::ENUM_LENGTH + MasterNft::U32_LENGTH; // duration_seconds + codec + sample_rate_hz
+
+    pub const MAX_DURATION_SECONDS: u32 = 6 * 60 * 60;
+    pub const SAMPLE_RATES_HZ: [u32; 9] = [
+        8_000, 16_000, 22_050, 32_000, 44_100, 48_000, 88_200, 96_000, 192_000,
+    ];
+
+    pub fn is_valid(&self) -> bool {
+        self.duration_seconds > 0
+            && self.duration_seconds <= Self::MAX_DURATION_SECONDS
+            && Self::SAMPLE_RATES_HZ.contains(&self.sample_rate_hz)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -33,10 +118,16 @@ impl MasterNft {
     pub const PUBKEY_LENGTH: usize = 32;
     pub const BOOL_LENGTH: usize = 1;
     pub const U8_LENGTH: usize = 1;
+    pub const U32_LENGTH: usize = 4;
     pub const I64_LENGTH: usize = 8;
     pub const STRING_PREFIX_LENGTH: usize = 4;
     pub const VECTOR_PREFIX_LENGTH: usize = 4;
     pub const ENUM_LENGTH: usize = 1; // Simple enum variant discriminator
+    pub const HASH_LENGTH: usize = 32;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
+
+    pub const CONTENT_FRAGMENT_PREFIX: &'static str = "#content=";
+    pub const CONTENT_FRAGMENT_LENGTH: usize = Self::CONTENT_FRAGMENT_PREFIX.len() + 2 * Self::HASH_LENGTH;
     
     pub const INIT_SPACE: usize = 
         Self::DISCRIMINATOR_LENGTH +
@@ -52,5 +143,156 @@ impl MasterNft {
         Self::BOOL_LENGTH +                     // is_transferable
         Self::ENUM_LENGTH +                     // status
         Self::I64_LENGTH +                      // created_at
-        Self::U8_LENGTH;                        // bump
+        Self::U8_LENGTH +                       // bump
+        Self::HASH_LENGTH +                     // audio_hash
+        Self::HASH_LENGTH +                     // artwork_hash
+        AudioSpec::SIZE +                       // audio_spec
+        Self::OPTION_PREFIX_LENGTH + Self::STRING_PREFIX_LENGTH + IdentifierRegistration::ISRC_LENGTH + // isrc
+        Self::HASH_LENGTH +                     // fingerprint
+        Self::OPTION_PREFIX_LENGTH + TrackVersion::SIZE + // version
+        Self::STRING_PREFIX_LENGTH + 200 +      // metadata_uri
+        Self::U8_LENGTH +                       // moderation_reason
+        Self::I64_LENGTH;                       // moderated_at
+
+    /// Whether the holder's token account should be frozen
+    pub fn is_locked(&self) -> bool {
+        !self.is_transferable || self.status.is_moderated()
+    }
+
+    /// SHA-256 over the audio hash, artwork hash and audio spec (little-endian, codec as its
+    /// variant index) the track was minted with
+    pub fn content_digest(&self) -> [u8; 32] {
+        hashv(&[
+            &self.audio_hash,
+            &self.artwork_hash,
+            &self.audio_spec.duration_seconds.to_le_bytes(),
+            &[self.audio_spec.codec as u8],
+            &self.audio_spec.sample_rate_hz.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// The URI written to token metadata, with the content digest appended as a fragment so
+    /// the off-chain JSON can be checked against what was registered on-chain
+    pub fn bound_metadata_uri(&self, metadata_uri: &str) -> String {
+        let digest: String = self
+            .content_digest()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("{}{}{}", metadata_uri, Self::CONTENT_FRAGMENT_PREFIX, digest)
+    }
+
+    /// Whether a metadata URI leaves room for the content fragment
+    pub fn is_valid_metadata_uri(metadata_uri: &str) -> bool {
+        !metadata_uri.is_empty()
+            && !metadata_uri.contains('#')
+            && metadata_uri.len() + Self::CONTENT_FRAGMENT_LENGTH <= mpl_token_metadata::MAX_URI_LENGTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master_nft(audio_spec: AudioSpec) -> MasterNft {
+        MasterNft {
+            title: "Track".to_string(),
+            description: String::new(),
+            artist_profile: Pubkey::default(),
+            audio_uri: String::new(),
+            artwork_uri: String::new(),
+            audio_hash: [1; 32],
+            artwork_hash: [2; 32],
+            audio_spec,
+            isrc: None,
+            fingerprint: [0; 32],
+            version: None,
+            metadata: Vec::new(),
+            metadata_uri: String::new(),
+            mint: Pubkey::default(),
+            is_transferable: true,
+            status: MasterNftStatus::Active,
+            moderation_reason: 0,
+            moderated_at: 0,
+            created_at: 0,
+            bump: 0,
+        }
+    }
+
+    fn audio_spec(duration_seconds: u32, sample_rate_hz: u32) -> AudioSpec {
+        AudioSpec { duration_seconds, codec: AudioCodec::Flac, sample_rate_hz }
+    }
+
+    #[test]
+    fn artists_publish_drafts_and_toggle_listing() {
+        use MasterNftStatus::*;
+        assert!(Draft.can_artist_transition(Active));
+        assert!(Active.can_artist_transition(Delisted));
+        assert!(Delisted.can_artist_transition(Active));
+        assert!(Active.can_artist_transition(Active));
+
+        assert!(!Active.can_artist_transition(Draft));
+        assert!(!Delisted.can_artist_transition(Draft));
+        assert!(!Draft.can_artist_transition(Delisted));
+        assert!(!Active.can_artist_transition(Frozen));
+        assert!(!Active.can_artist_transition(TakenDown));
+    }
+
+    #[test]
+    fn artists_cannot_lift_a_moderation_lock() {
+        use MasterNftStatus::*;
+        for locked in [Frozen, TakenDown] {
+            for to in [Active, Delisted, Draft, Frozen, TakenDown] {
+                assert!(!locked.can_artist_transition(to));
+            }
+        }
+    }
+
+    #[test]
+    fn moderators_lock_any_track_and_lift_locks_to_published() {
+        use MasterNftStatus::*;
+        for from in [Active, Delisted, Draft] {
+            assert!(from.can_moderator_transition(Frozen));
+            assert!(from.can_moderator_transition(TakenDown));
+            assert!(!from.can_moderator_transition(Active));
+            assert!(!from.can_moderator_transition(Delisted));
+        }
+
+        assert!(Frozen.can_moderator_transition(TakenDown));
+        assert!(!Frozen.can_moderator_transition(Frozen));
+        assert!(TakenDown.can_moderator_transition(Active));
+        assert!(TakenDown.can_moderator_transition(Delisted));
+        assert!(!TakenDown.can_moderator_transition(Draft));
+    }
+
+    #[test]
+    fn audio_spec_is_valid_checks_duration_and_sample_rate() {
+        assert!(audio_spec(180, 44_100).is_valid());
+        assert!(audio_spec(AudioSpec::MAX_DURATION_SECONDS, 192_000).is_valid());
+        assert!(!audio_spec(0, 44_100).is_valid());
+        assert!(!audio_spec(AudioSpec::MAX_DURATION_SECONDS + 1, 44_100).is_valid());
+        assert!(!audio_spec(180, 44_000).is_valid());
+    }
+
+    #[test]
+    fn bound_metadata_uri_appends_the_content_digest() {
+        let master_nft = master_nft(audio_spec(180, 44_100));
+        assert_eq!(
+            master_nft.bound_metadata_uri("https://example.com/track.json"),
+            "https://example.com/track.json#content=70404abfb931c6f8e71aa7e04b62088278331d9ce757b86ce3b48476f203d517"
+        );
+
+        let other = MasterNft { audio_spec: audio_spec(181, 44_100), ..master_nft.clone() };
+        assert_ne!(other.content_digest(), master_nft.content_digest());
+    }
+
+    #[test]
+    fn is_valid_metadata_uri_leaves_room_for_the_fragment() {
+        let limit = mpl_token_metadata::MAX_URI_LENGTH - MasterNft::CONTENT_FRAGMENT_LENGTH;
+        assert!(MasterNft::is_valid_metadata_uri(&"a".repeat(limit)));
+        assert!(!MasterNft::is_valid_metadata_uri(&"a".repeat(limit + 1)));
+        assert!(!MasterNft::is_valid_metadata_uri(""));
+        assert!(!MasterNft::is_valid_metadata_uri("https://example.com/track.json#v2"));
+    }
 }
\ No newline at end of file
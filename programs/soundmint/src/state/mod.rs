@@ -4,10 +4,36 @@ pub mod royalty_split;
 pub mod royalty_nft;
 pub mod treasury;
 pub mod revenue_tracker;
+pub mod revenue_epoch;
+pub mod admin_proposal;
+pub mod admin_role;
+pub mod fee_override;
+pub mod fee_voucher;
+pub mod artist_handle;
+pub mod artist_delegate;
+pub mod label;
+pub mod verification_request;
+pub mod identifier_registration;
+pub mod audio_fingerprint;
+pub mod derivative_link;
+pub mod legacy;
 
 pub use artist_profile::*;
 pub use master_nft::*;
 pub use royalty_nft::*;
 pub use royalty_split::*;
 pub use treasury::*;
-pub use revenue_tracker::*;
\ No newline at end of file
+pub use revenue_tracker::*;
+pub use revenue_epoch::*;
+pub use admin_proposal::*;
+pub use admin_role::*;
+pub use fee_override::*;
+pub use fee_voucher::*;
+pub use artist_handle::*;
+pub use artist_delegate::*;
+pub use label::*;
+pub use verification_request::*;
+pub use identifier_registration::*;
+pub use audio_fingerprint::*;
+pub use derivative_link::*;
+pub use legacy::*;
\ No newline at end of file
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use crate::constants::TOTAL_BASIS_POINTS;
+
+#[account]
+#[derive(Default)]
+pub struct RevenueEpoch {
+    pub master_nft: Pubkey,
+    pub period: u32,
+    pub total_revenue: u64,
+    pub streaming_revenue: u64,
+    pub sales_revenue: u64,
+    pub other_revenue: u64,
+    pub total_claimed: u64,
+    pub is_closed: bool,
+    pub created_at: i64,
+    pub closed_at: i64,
+    pub bump: u8,
+    pub claimed_basis_points: u16, // Share of royalty NFTs that have a statement for this epoch
+    pub statement_count: u32,      // Statements to close along with the epoch
+    pub payer: Pubkey,             // Receives the rent back when the epoch is closed
+}
+
+#[account]
+pub struct RoyaltyStatement {
+    pub revenue_epoch: Pubkey,
+    pub royalty_nft: Pubkey,
+    pub period: u32,
+    pub share_basis_points: u16,
+    pub gross_amount: u64,
+    pub platform_fee: u64,
+    pub net_amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+    pub claimant: Pubkey, // Paid the statement's rent and receives it back
+}
+
+impl RevenueEpoch {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const BOOL_LENGTH: usize = 1;
+    pub const U16_LENGTH: usize = 2;
+    pub const U32_LENGTH: usize = 4;
+    pub const U64_LENGTH: usize = 8;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // master_nft
+        Self::U32_LENGTH +        // period
+        Self::U64_LENGTH +        // total_revenue
+        Self::U64_LENGTH +        // streaming_revenue
+        Self::U64_LENGTH +        // sales_revenue
+        Self::U64_LENGTH +        // other_revenue
+        Self::U64_LENGTH +        // total_claimed
+        Self::BOOL_LENGTH +       // is_closed
+        Self::I64_LENGTH +        // created_at
+        Self::I64_LENGTH +        // closed_at
+        Self::U8_LENGTH +         // bump
+        Self::U16_LENGTH +        // claimed_basis_points
+        Self::U32_LENGTH +        // statement_count
+        Self::PUBKEY_LENGTH;      // payer
+
+    /// Returns the UTC calendar month containing `timestamp`, encoded as `YYYYMM`.
+    pub fn period_for_timestamp(timestamp: i64) -> u32 {
+        // Civil-from-days conversion on the proleptic Gregorian calendar
+        let days = timestamp.div_euclid(86_400);
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        (year * 100 + month) as u32
+    }
+
+    pub fn is_valid_period(period: u32) -> bool {
+        (1..=12).contains(&(period % 100))
+    }
+
+    /// Closed with nothing left to pay out to the royalty NFTs that are still outstanding.
+    /// Holders whose share rounds down to nothing record a zero statement to settle it.
+    pub fn is_settled(&self, minted_basis_points: u16) -> bool {
+        let unclaimed_basis_points = minted_basis_points.saturating_sub(self.claimed_basis_points) as u64;
+        let unclaimed_revenue = self.total_revenue
+            .checked_mul(unclaimed_basis_points).unwrap()
+            / TOTAL_BASIS_POINTS as u64;
+        self.is_closed && (unclaimed_revenue == 0 || self.total_claimed >= self.total_revenue)
+    }
+
+    pub fn record_revenue(&mut self, amount: u64, source: &str) {
+        self.total_revenue = self.total_revenue.checked_add(amount).unwrap();
+
+        if source == "streaming" {
+            self.streaming_revenue = self.streaming_revenue.checked_add(amount).unwrap();
+        } else if source == "sales" {
+            self.sales_revenue = self.sales_revenue.checked_add(amount).unwrap();
+        } else {
+            self.other_revenue = self.other_revenue.checked_add(amount).unwrap();
+        }
+    }
+}
+
+impl RoyaltyStatement {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const U16_LENGTH: usize = 2;
+    pub const U32_LENGTH: usize = 4;
+    pub const U64_LENGTH: usize = 8;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +     // revenue_epoch
+        Self::PUBKEY_LENGTH +     // royalty_nft
+        Self::U32_LENGTH +        // period
+        Self::U16_LENGTH +        // share_basis_points
+        Self::U64_LENGTH +        // gross_amount
+        Self::U64_LENGTH +        // platform_fee
+        Self::U64_LENGTH +        // net_amount
+        Self::I64_LENGTH +        // claimed_at
+        Self::U8_LENGTH +         // bump
+        Self::PUBKEY_LENGTH;      // claimant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn period_for_timestamp_uses_the_utc_calendar_month() {
+        assert_eq!(RevenueEpoch::period_for_timestamp(0), 197001);
+        assert_eq!(RevenueEpoch::period_for_timestamp(-1), 196912);
+        assert_eq!(RevenueEpoch::period_for_timestamp(1_704_067_199), 202312);
+        assert_eq!(RevenueEpoch::period_for_timestamp(1_704_067_200), 202401);
+    }
+
+    #[test]
+    fn period_for_timestamp_handles_leap_days() {
+        assert_eq!(RevenueEpoch::period_for_timestamp(951_782_400), 200002); // 2000-02-29
+        assert_eq!(RevenueEpoch::period_for_timestamp(951_868_800), 200003); // 2000-03-01
+        assert_eq!(RevenueEpoch::period_for_timestamp(4_107_542_400), 210003); // 2100-03-01
+    }
+
+    #[test]
+    fn is_settled_once_outstanding_shares_have_claimed() {
+        let mut revenue_epoch = RevenueEpoch {
+            total_revenue: 1_000,
+            is_closed: true,
+            ..RevenueEpoch::default()
+        };
+        assert!(!revenue_epoch.is_settled(10_000));
+        assert!(revenue_epoch.is_settled(0));
+
+        revenue_epoch.claimed_basis_points = 6_000;
+        assert!(!revenue_epoch.is_settled(10_000));
+        assert!(revenue_epoch.is_settled(6_000));
+
+        revenue_epoch.is_closed = false;
+        assert!(!revenue_epoch.is_settled(6_000));
+    }
+
+    #[test]
+    fn is_settled_tolerates_rounding_dust() {
+        let revenue_epoch = RevenueEpoch {
+            total_revenue: 3,
+            claimed_basis_points: 7_000,
+            is_closed: true,
+            ..RevenueEpoch::default()
+        };
+        assert!(revenue_epoch.is_settled(10_000));
+    }
+
+    #[test]
+    fn is_valid_period_checks_the_month() {
+        assert!(RevenueEpoch::is_valid_period(202401));
+        assert!(RevenueEpoch::is_valid_period(202412));
+        assert!(!RevenueEpoch::is_valid_period(202400));
+        assert!(!RevenueEpoch::is_valid_period(202413));
+    }
+}
@@ -5,7 +5,7 @@ pub struct RoyaltyNft {
     pub master_nft: Pubkey,
     pub mint: Pubkey,
     pub share_basis_points: u16,
-    pub amount_claimed: u64,
+    pub amount_claimed: u64, // Paid from revenue collected before epochs; later payouts are on statements
     pub last_claimed_at: i64,
     pub created_at: i64,
     pub bump: u8,
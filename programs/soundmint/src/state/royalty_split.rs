@@ -9,6 +9,13 @@ pub struct RoyaltySplit {
     pub created_at: i64,
     pub last_revenue_timestamp: i64,
     pub bump: u8,
+    pub revenue_migrated: bool, // Revenue collected before epochs has been moved into the legacy epoch
+    pub revenue_epoch_count: u32, // Revenue epochs that still have to be closed before the track can be
+    pub royalty_nft_count: u16,   // Royalty NFTs that still have to be burned before the track can be
+    pub minted_basis_points: u16, // Share held by outstanding royalty NFTs
+    pub upstream: Vec<UpstreamShare>, // Accepted parents this track's revenue cascades into
+    pub closed_through_period: u32,   // Latest period whose epoch was settled and closed out
+    pub minted_shares: Vec<MintedShare>, // Share each collaborator has ever minted as royalty NFTs
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -19,18 +26,42 @@ pub struct Collaborator {
     pub amount_claimed: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpstreamShare {
+    pub parent: Pubkey,      // Parent master NFT
+    pub basis_points: u16,
+    pub amount_paid: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintedShare {
+    pub collaborator: Pubkey,
+    pub basis_points: u16,
+}
+
+impl MintedShare {
+    pub const SIZE: usize = RoyaltySplit::PUBKEY_LENGTH + RoyaltySplit::U16_LENGTH; // collaborator + basis_points
+}
+
+impl UpstreamShare {
+    pub const SIZE: usize = RoyaltySplit::PUBKEY_LENGTH + RoyaltySplit::U16_LENGTH + RoyaltySplit::U64_LENGTH; // parent + basis_points + amount_paid
+}
+
 impl RoyaltySplit {
     pub const DISCRIMINATOR_LENGTH: usize = 8;
     pub const PUBKEY_LENGTH: usize = 32;
     pub const U16_LENGTH: usize = 2;
+    pub const U32_LENGTH: usize = 4;
     pub const U64_LENGTH: usize = 8;
     pub const I64_LENGTH: usize = 8;
     pub const U8_LENGTH: usize = 1;
+    pub const BOOL_LENGTH: usize = 1;
     pub const STRING_PREFIX_LENGTH: usize = 4;
     pub const VECTOR_PREFIX_LENGTH: usize = 4;
     
     // Assuming a maximum of 10 collaborators for space calculation
     pub const MAX_COLLABORATORS: usize = 10;
+    pub const MAX_UPSTREAM_SHARES: usize = 3;
     pub const COLLABORATOR_SIZE: usize = 
         Self::PUBKEY_LENGTH +                // address
         Self::STRING_PREFIX_LENGTH + 50 +    // name (estimating 50 bytes)
@@ -46,5 +77,97 @@ impl RoyaltySplit {
         Self::U64_LENGTH +                           // total_revenue_collected
         Self::I64_LENGTH +                           // created_at
         Self::I64_LENGTH +                           // last_revenue_timestamp
-        Self::U8_LENGTH;                             // bump
-}
\ No newline at end of file
+        Self::U8_LENGTH +                            // bump
+        Self::BOOL_LENGTH +                          // revenue_migrated
+        Self::U32_LENGTH +                           // revenue_epoch_count
+        Self::U16_LENGTH +                           // royalty_nft_count
+        Self::U16_LENGTH +                           // minted_basis_points
+        Self::VECTOR_PREFIX_LENGTH +                 // upstream vector prefix
+        Self::MAX_UPSTREAM_SHARES * UpstreamShare::SIZE + // upstream
+        Self::U32_LENGTH +                           // closed_through_period
+        Self::VECTOR_PREFIX_LENGTH +                 // minted_shares vector prefix
+        Self::MAX_COLLABORATORS * MintedShare::SIZE; // minted_shares
+}
+
+impl RoyaltySplit {
+    /// Every epoch and royalty NFT of the track is gone, so nothing can be left unclaimed
+    pub fn is_settled(&self) -> bool {
+        self.revenue_epoch_count == 0 && self.royalty_nft_count == 0
+    }
+
+    /// A closed-out epoch's account is gone, so its period and every earlier one stay sealed
+    /// rather than being re-created
+    pub fn accepts_period(&self, period: u32) -> bool {
+        period > self.closed_through_period
+    }
+
+    pub fn minted_share_of(&self, collaborator: &Pubkey) -> u16 {
+        self.minted_shares
+            .iter()
+            .find(|share| share.collaborator == *collaborator)
+            .map_or(0, |share| share.basis_points)
+    }
+
+    /// Minted shares are never given back, so burning a royalty NFT can't free it to be minted
+    /// and claimed a second time
+    pub fn record_minted_share(&mut self, collaborator: Pubkey, basis_points: u16) {
+        match self.minted_shares.iter_mut().find(|share| share.collaborator == collaborator) {
+            Some(share) => share.basis_points = share.basis_points.checked_add(basis_points).unwrap(),
+            None => self.minted_shares.push(MintedShare { collaborator, basis_points }),
+        }
+    }
+
+    pub fn upstream_basis_points(&self) -> u16 {
+        self.upstream.iter().map(|share| share.basis_points).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn royalty_split() -> RoyaltySplit {
+        RoyaltySplit {
+            master_nft: Pubkey::new_unique(),
+            collaborators: Vec::new(),
+            total_basis_points: 0,
+            total_revenue_collected: 0,
+            created_at: 0,
+            last_revenue_timestamp: 0,
+            bump: 0,
+            revenue_migrated: true,
+            revenue_epoch_count: 0,
+            royalty_nft_count: 0,
+            minted_basis_points: 0,
+            upstream: Vec::new(),
+            closed_through_period: 0,
+            minted_shares: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn minted_shares_accumulate_per_collaborator() {
+        let mut royalty_split = royalty_split();
+        let (producer, featured) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        royalty_split.record_minted_share(producer, 1_000);
+        royalty_split.record_minted_share(featured, 500);
+        royalty_split.record_minted_share(producer, 1_000);
+
+        assert_eq!(royalty_split.minted_share_of(&producer), 2_000);
+        assert_eq!(royalty_split.minted_share_of(&featured), 500);
+        assert_eq!(royalty_split.minted_share_of(&Pubkey::new_unique()), 0);
+        assert_eq!(royalty_split.minted_shares.len(), 2);
+    }
+
+    #[test]
+    fn accepts_period_after_the_closed_out_watermark() {
+        let royalty_split = RoyaltySplit {
+            closed_through_period: 202405,
+            ..royalty_split()
+        };
+        assert!(!royalty_split.accepts_period(202404));
+        assert!(!royalty_split.accepts_period(202405));
+        assert!(royalty_split.accepts_period(202406));
+    }
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::TOTAL_BASIS_POINTS;
+use crate::state::FeeOverride;
 
 #[account]
 pub struct Treasury {
@@ -11,6 +13,59 @@ pub struct Treasury {
     pub created_at: i64,
     pub updated_at: i64,
     pub bump: u8,
+    pub pending_authority: Option<Pubkey>,
+    pub guardian: Pubkey,
+    pub paused_flags: u8,
+    pub verified_mint_fee: u64,
+    pub verified_platform_fee_basis_points: u16,
+    pub referral_fee_basis_points: u16, // Share of each mint fee routed to the artist's referrer
+    pub pending_config: Option<PendingTreasuryConfig>,
+    pub total_referral_rewards_paid: u64,
+    pub mint_fees: FeeLedger,
+    pub claim_fees: FeeLedger,
+    pub streaming_fees: FeeLedger,
+    pub admins: Vec<Pubkey>,
+    pub admin_threshold: u8,
+    pub proposal_count: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingTreasuryConfig {
+    pub mint_fee: Option<u64>,
+    pub platform_fee_basis_points: Option<u16>,
+    pub verified_mint_fee: Option<u64>,
+    pub verified_platform_fee_basis_points: Option<u16>,
+    pub referral_fee_basis_points: Option<u16>,
+    pub treasury_wallet: Option<Pubkey>,
+    pub effective_at: i64,
+}
+
+/// Fees that apply to a particular artist after tiers and overrides are resolved
+#[derive(Clone, Copy)]
+pub struct FeeSchedule {
+    pub mint_fee: u64,
+    pub platform_fee_basis_points: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeLedger {
+    pub collected: u64,
+    pub withdrawn: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeCategory {
+    Mint,
+    Claims,
+    Streaming,
+}
+
+impl FeeLedger {
+    pub const SIZE: usize = Treasury::U64_LENGTH + Treasury::U64_LENGTH; // collected + withdrawn
+
+    pub fn available(&self) -> u64 {
+        self.collected.saturating_sub(self.withdrawn)
+    }
 }
 
 impl Treasury {
@@ -20,6 +75,10 @@ impl Treasury {
     pub const U16_LENGTH: usize = 2;
     pub const I64_LENGTH: usize = 8;
     pub const U8_LENGTH: usize = 1;
+    pub const OPTION_PREFIX_LENGTH: usize = 1;
+    pub const VECTOR_PREFIX_LENGTH: usize = 4;
+    
+    pub const MAX_ADMINS: usize = 10;
     
     pub const INIT_SPACE: usize = 
         Self::DISCRIMINATOR_LENGTH +
@@ -31,5 +90,122 @@ impl Treasury {
         Self::U64_LENGTH +        // total_revenue_collected
         Self::I64_LENGTH +        // created_at
         Self::I64_LENGTH +        // updated_at
-        Self::U8_LENGTH;          // bump
+        Self::U8_LENGTH +         // bump
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH + // pending_authority
+        Self::PUBKEY_LENGTH +     // guardian
+        Self::U8_LENGTH +         // paused_flags
+        Self::U64_LENGTH +        // verified_mint_fee
+        Self::U16_LENGTH +        // verified_platform_fee_basis_points
+        Self::U16_LENGTH +        // referral_fee_basis_points
+        Self::OPTION_PREFIX_LENGTH +                   // pending_config
+        Self::OPTION_PREFIX_LENGTH + Self::U64_LENGTH +    // pending mint_fee
+        Self::OPTION_PREFIX_LENGTH + Self::U16_LENGTH +    // pending platform_fee_basis_points
+        Self::OPTION_PREFIX_LENGTH + Self::U64_LENGTH +    // pending verified_mint_fee
+        Self::OPTION_PREFIX_LENGTH + Self::U16_LENGTH +    // pending verified_platform_fee_basis_points
+        Self::OPTION_PREFIX_LENGTH + Self::U16_LENGTH +    // pending referral_fee_basis_points
+        Self::OPTION_PREFIX_LENGTH + Self::PUBKEY_LENGTH + // pending treasury_wallet
+        Self::I64_LENGTH +                             // pending effective_at
+        Self::U64_LENGTH +        // total_referral_rewards_paid
+        FeeLedger::SIZE +         // mint_fees
+        FeeLedger::SIZE +         // claim_fees
+        FeeLedger::SIZE +         // streaming_fees
+        Self::VECTOR_PREFIX_LENGTH +                   // admins vector prefix
+        Self::MAX_ADMINS * Self::PUBKEY_LENGTH +       // admins
+        Self::U8_LENGTH +         // admin_threshold
+        Self::U64_LENGTH;         // proposal_count
+
+    /// Once a threshold is set, admin actions must go through multisig proposals
+    pub fn is_multisig_enabled(&self) -> bool {
+        self.admin_threshold > 0
+    }
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admins.contains(key)
+    }
+
+    pub fn fee_ledger_mut(&mut self, category: FeeCategory) -> &mut FeeLedger {
+        match category {
+            FeeCategory::Mint => &mut self.mint_fees,
+            FeeCategory::Claims => &mut self.claim_fees,
+            FeeCategory::Streaming => &mut self.streaming_fees,
+        }
+    }
+
+    /// Books a fee that has been transferred into the treasury PDA
+    pub fn record_fee(&mut self, category: FeeCategory, amount: u64) {
+        let ledger = self.fee_ledger_mut(category);
+        ledger.collected = ledger.collected.checked_add(amount).unwrap();
+        self.total_revenue_collected = self.total_revenue_collected.checked_add(amount).unwrap();
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused_flags & flag != 0
+    }
+
+    /// Applies a scheduled config change once its timelock has passed.
+    /// Returns true if the treasury was modified.
+    pub fn apply_pending_config(&mut self, now: i64) -> bool {
+        let pending = match &self.pending_config {
+            Some(pending) if pending.effective_at <= now => pending.clone(),
+            _ => return false,
+        };
+
+        if let Some(fee) = pending.mint_fee {
+            self.mint_fee = fee;
+        }
+        if let Some(fee_basis_points) = pending.platform_fee_basis_points {
+            self.platform_fee_basis_points = fee_basis_points;
+        }
+        if let Some(fee) = pending.verified_mint_fee {
+            self.verified_mint_fee = fee;
+        }
+        if let Some(fee_basis_points) = pending.verified_platform_fee_basis_points {
+            self.verified_platform_fee_basis_points = fee_basis_points;
+        }
+        if let Some(fee_basis_points) = pending.referral_fee_basis_points {
+            self.referral_fee_basis_points = fee_basis_points;
+        }
+        if let Some(wallet) = pending.treasury_wallet {
+            self.treasury_wallet = wallet;
+        }
+
+        self.pending_config = None;
+        self.updated_at = now;
+        true
+    }
+
+    /// Resolves the fees for an artist at `now`: a per-artist override wins over
+    /// the verified tier, which wins over the default schedule
+    pub fn fee_schedule(&self, is_verified: bool, fee_override: Option<&FeeOverride>, now: i64) -> FeeSchedule {
+        let (mint_fee, platform_fee_basis_points) = if is_verified {
+            (self.verified_mint_fee, self.verified_platform_fee_basis_points)
+        } else {
+            (self.mint_fee, self.platform_fee_basis_points)
+        };
+        let (override_mint_fee, override_platform_fee_basis_points) = fee_override
+            .map(|fee_override| fee_override.effective_fees(now))
+            .unwrap_or((None, None));
+
+        FeeSchedule {
+            mint_fee: override_mint_fee.unwrap_or(mint_fee),
+            platform_fee_basis_points: override_platform_fee_basis_points
+                .unwrap_or(platform_fee_basis_points),
+        }
+    }
+
+    /// Portion of a mint fee owed to the artist's referrer
+    pub fn referral_reward(&self, mint_fee: u64) -> u64 {
+        mint_fee
+            .checked_mul(self.referral_fee_basis_points as u64).unwrap()
+            .checked_div(TOTAL_BASIS_POINTS as u64).unwrap()
+    }
+
+    /// The treasury wallet in force at `now`, including a matured pending change
+    pub fn effective_treasury_wallet(&self, now: i64) -> Pubkey {
+        match &self.pending_config {
+            Some(PendingTreasuryConfig { treasury_wallet: Some(wallet), effective_at, .. })
+                if *effective_at <= now => *wallet,
+            _ => self.treasury_wallet,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::VerificationTier;
+
+#[account]
+pub struct VerificationRequest {
+    pub artist_profile: Pubkey,
+    pub evidence_uri: String,                 // Links, press or distributor proof backing the request
+    pub requested_tier: VerificationTier,
+    pub status: VerificationStatus,
+    pub submitted_at: i64,
+    pub history: Vec<VerificationDecision>,   // Most recent review decisions, oldest first
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerificationDecision {
+    pub approved: bool,
+    pub tier: VerificationTier,
+    pub reason_code: u8,
+    pub reviewer: Pubkey,
+    pub decided_at: i64,
+}
+
+impl VerificationDecision {
+    pub const SIZE: usize =
+        VerificationRequest::BOOL_LENGTH +    // approved
+        VerificationRequest::ENUM_LENGTH +    // tier
+        VerificationRequest::U8_LENGTH +      // reason_code
+        VerificationRequest::PUBKEY_LENGTH +  // reviewer
+        VerificationRequest::I64_LENGTH;      // decided_at
+}
+
+impl VerificationRequest {
+    pub const DISCRIMINATOR_LENGTH: usize = 8;
+    pub const PUBKEY_LENGTH: usize = 32;
+    pub const I64_LENGTH: usize = 8;
+    pub const U8_LENGTH: usize = 1;
+    pub const BOOL_LENGTH: usize = 1;
+    pub const ENUM_LENGTH: usize = 1;
+    pub const STRING_PREFIX_LENGTH: usize = 4;
+    pub const VECTOR_PREFIX_LENGTH: usize = 4;
+
+    pub const MAX_EVIDENCE_URI_LENGTH: usize = 200;
+    pub const MAX_HISTORY: usize = 5;
+
+    pub const INIT_SPACE: usize =
+        Self::DISCRIMINATOR_LENGTH +
+        Self::PUBKEY_LENGTH +                                        // artist_profile
+        Self::STRING_PREFIX_LENGTH + Self::MAX_EVIDENCE_URI_LENGTH + // evidence_uri
+        Self::ENUM_LENGTH +                                          // requested_tier
+        Self::ENUM_LENGTH +                                          // status
+        Self::I64_LENGTH +                                           // submitted_at
+        Self::VECTOR_PREFIX_LENGTH +                                 // history vector prefix
+        Self::MAX_HISTORY * VerificationDecision::SIZE +             // history
+        Self::U8_LENGTH;                                             // bump
+
+    /// Appends a review decision, dropping the oldest once the trail is full
+    pub fn record_decision(&mut self, decision: VerificationDecision) {
+        if self.history.len() >= Self::MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(decision);
+    }
+}
@@ -0,0 +1,122 @@
+// Client helpers for the tests. These mirror checks the program does on-chain, so off-chain
+// data lines up with what the accounts record.
+
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { Soundmint } from "../target/types/soundmint";
+
+// Variant order of the on-chain AudioCodec enum
+export const AUDIO_CODECS = ["mp3", "aac", "ogg", "opus", "flac", "alac", "wav"] as const;
+
+export type AudioCodec = (typeof AUDIO_CODECS)[number];
+
+export interface AudioSpec {
+  durationSeconds: number;
+  codec: { [codec in AudioCodec]?: {} };
+  sampleRateHz: number;
+}
+
+const CONTENT_FRAGMENT_PREFIX = "#content=";
+
+const hex = (bytes: number[] | Uint8Array): string => Buffer.from(bytes).toString("hex");
+
+// SHA-256 over the audio hash, artwork hash and audio spec, matching MasterNft::content_digest
+export const contentDigest = (audioHash: number[], artworkHash: number[], audioSpec: AudioSpec): Buffer => {
+  const codec = AUDIO_CODECS.indexOf(Object.keys(audioSpec.codec)[0] as AudioCodec);
+  if (codec < 0) {
+    throw new Error("Unknown audio codec");
+  }
+
+  const spec = Buffer.alloc(9);
+  spec.writeUInt32LE(audioSpec.durationSeconds, 0);
+  spec.writeUInt8(codec, 4);
+  spec.writeUInt32LE(audioSpec.sampleRateHz, 5);
+
+  return createHash("sha256")
+    .update(Buffer.from(audioHash))
+    .update(Buffer.from(artworkHash))
+    .update(spec)
+    .digest();
+};
+
+// The URI the program writes to token metadata for a given metadata_uri
+export const boundMetadataUri = (
+  metadataUri: string,
+  audioHash: number[],
+  artworkHash: number[],
+  audioSpec: AudioSpec
+): string => `${metadataUri}${CONTENT_FRAGMENT_PREFIX}${hex(contentDigest(audioHash, artworkHash, audioSpec))}`;
+
+// Off-chain JSON for a master NFT. The content fields are what the token metadata URI's
+// fragment commits to, so anyone can recompute the digest from the JSON alone.
+export const buildTrackMetadata = (track: {
+  title: string;
+  description: string;
+  audioUri: string;
+  artworkUri: string;
+  audioHash: number[];
+  artworkHash: number[];
+  audioSpec: AudioSpec;
+  attributes?: { key: string; value: string }[];
+}) => ({
+  name: track.title,
+  description: track.description,
+  image: track.artworkUri,
+  animation_url: track.audioUri,
+  attributes: (track.attributes ?? []).map(({ key, value }) => ({ trait_type: key, value })),
+  properties: {
+    category: "audio",
+    files: [
+      { uri: track.audioUri, type: "audio", sha256: hex(track.audioHash) },
+      { uri: track.artworkUri, type: "image", sha256: hex(track.artworkHash) },
+    ],
+    audio_spec: {
+      duration_seconds: track.audioSpec.durationSeconds,
+      codec: Object.keys(track.audioSpec.codec)[0],
+      sample_rate_hz: track.audioSpec.sampleRateHz,
+    },
+    content_digest: hex(contentDigest(track.audioHash, track.artworkHash, track.audioSpec)),
+  },
+});
+
+// ISRC and UPC registrations are keyed by kind and canonical code
+export const IDENTIFIER_KIND = { isrc: 0, upc: 1 } as const;
+
+// Uppercase with the hyphens of the display form removed
+export const canonicalIsrc = (isrc: string): string => isrc.replace(/-/g, "").toUpperCase();
+
+// EAN-13, so a UPC-A and its zero-padded EAN-13 spelling resolve to the same registration
+export const canonicalUpc = (upc: string): string => {
+  const digits = upc.replace(/[\s-]/g, "");
+  return /^\d{12}$/.test(digits) ? `0${digits}` : digits;
+};
+
+// Seeded by a SHA-256 of the code, matching IdentifierRegistration::code_seed
+export const findIdentifierPDA = (programId: PublicKey, kind: number, code: string): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("identifier"), Buffer.from([kind]), createHash("sha256").update(code).digest()],
+    programId
+  )[0];
+
+// Resolves the master NFT currently holding an ISRC, or null if it is unregistered
+export const lookupMasterNftByIsrc = async (
+  program: Program<Soundmint>,
+  isrc: string
+): Promise<PublicKey | null> => {
+  const registration = await program.account.identifierRegistration.fetchNullable(
+    findIdentifierPDA(program.programId, IDENTIFIER_KIND.isrc, canonicalIsrc(isrc))
+  );
+  return registration ? registration.holder : null;
+};
+
+// Resolves the collection currently holding a UPC, or null if it is unregistered
+export const lookupCollectionByUpc = async (
+  program: Program<Soundmint>,
+  upc: string
+): Promise<PublicKey | null> => {
+  const registration = await program.account.identifierRegistration.fetchNullable(
+    findIdentifierPDA(program.programId, IDENTIFIER_KIND.upc, canonicalUpc(upc))
+  );
+  return registration ? registration.holder : null;
+};
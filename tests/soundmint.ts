@@ -9,6 +9,16 @@ import {
   Transaction
 } from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
+import {
+  IDENTIFIER_KIND,
+  boundMetadataUri,
+  buildTrackMetadata,
+  canonicalUpc,
+  findIdentifierPDA,
+  lookupCollectionByUpc,
+  lookupMasterNftByIsrc,
+} from "./soundmint-client";
 
 describe("soundmint", () => {
   // Configure the client to use the local cluster
@@ -55,6 +65,56 @@ describe("soundmint", () => {
     program.programId
   );
 
+  // Artist handles are keyed by the hash of the lowercase alphanumeric name
+  const findArtistHandlePDA = (name: string): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("artist_handle"),
+        createHash("sha256").update(name.toLowerCase().replace(/[^a-z0-9]/g, "")).digest(),
+      ],
+      program.programId
+    )[0];
+
+  // Each recording's perceptual fingerprint maps to a single registry entry
+  const findAudioFingerprintPDA = (fingerprint: number[]): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("audio_fingerprint"), Buffer.from(fingerprint)],
+      program.programId
+    )[0];
+
+  // Revenue epochs are keyed by UTC calendar month, encoded as YYYYMM
+  const periodFor = (date: Date): number => date.getUTCFullYear() * 100 + date.getUTCMonth() + 1;
+  const findRevenueEpochPDA = (masterNft: PublicKey, period: number): PublicKey => {
+    const periodBytes = Buffer.alloc(4);
+    periodBytes.writeUInt32LE(period);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("revenue_epoch"), masterNft.toBuffer(), periodBytes],
+      program.programId
+    )[0];
+  };
+
+  // Token metadata stores key, update authority, mint, then name, symbol and uri as
+  // length-prefixed strings padded with NULs
+  const readTokenMetadataUri = async (metadataAccount: PublicKey): Promise<string> => {
+    const data = (await connection.getAccountInfo(metadataAccount)).data;
+    let offset = 1 + 32 + 32;
+    for (let field = 0; field < 2; field++) {
+      offset += 4 + data.readUInt32LE(offset);
+    }
+    const length = data.readUInt32LE(offset);
+    return data.subarray(offset + 4, offset + 4 + length).toString("utf8").replace(/\0+$/, "");
+  };
+
+  // SPL mints store the mint authority as a COption<Pubkey>, followed by the u64 supply
+  const [nftAuthorityPDA] = PublicKey.findProgramAddressSync([Buffer.from("nft_authority")], program.programId);
+  const readMint = async (mint: PublicKey): Promise<{ mintAuthority: PublicKey | null; supply: BN }> => {
+    const data = (await connection.getAccountInfo(mint)).data;
+    return {
+      mintAuthority: data.readUInt32LE(0) === 0 ? null : new PublicKey(data.subarray(4, 36)),
+      supply: new BN(data.subarray(36, 44), "le"),
+    };
+  };
+
   console.log({ treasuryPDA, artistProfilePDA });
 
   // Master NFT details (used later)
@@ -62,6 +122,11 @@ describe("soundmint", () => {
   const masterNftDescription = "An immersive electronic music experience";
   const audioUri = "https://soundmint.com/audio/cosmic-journey.mp3";
   const artworkUri = "https://soundmint.com/artwork/cosmic-journey.jpg";
+  const audioHash = Array.from(createHash("sha256").update("cosmic-journey-audio").digest());
+  const artworkHash = Array.from(createHash("sha256").update("cosmic-journey-artwork").digest());
+  const audioSpec = { durationSeconds: 245, codec: { flac: {} }, sampleRateHz: 44100 };
+  const isrc = "USS1Z9900001";
+  const fingerprint = Array.from(createHash("sha256").update("cosmic-journey-fingerprint").digest());
   const metadata = [
     { key: "genre", value: "Electronic" },
     { key: "bpm", value: "128" },
@@ -165,10 +230,11 @@ describe("soundmint", () => {
 
       // Create the artist profile
       const txSignature = await program.methods
-        .createArtistProfile(name, description, profileImageUri)
+        .createArtistProfile(name, description, profileImageUri, null)
         .accounts({
           authority: accounts.artist,
           artistProfile: accounts.artistProfile,
+          artistHandle: findArtistHandlePDA(name),
           systemProgram: accounts.systemProgram,
         })
         .signers([artist])
@@ -185,6 +251,10 @@ describe("soundmint", () => {
       expect(artistProfileAccount.profileImageUri).to.equal(profileImageUri);
       expect(artistProfileAccount.isVerified).to.be.false;
       expect(artistProfileAccount.trackCount.toString()).to.equal("0");
+
+      const artistHandleAccount = await program.account.artistHandle.fetch(findArtistHandlePDA(name));
+      expect(artistHandleAccount.handle).to.equal("cosmicrhythms");
+      expect(artistHandleAccount.artistProfile.toString()).to.equal(artistProfilePDA.toString());
     } catch (e) {
       console.error("Error creating artist profile:", e);
       throw e;
@@ -216,6 +286,8 @@ describe("soundmint", () => {
         .accounts({
           authority: accounts.artist,
           artistProfile: accounts.artistProfile,
+          currentHandle: findArtistHandlePDA("Cosmic Rhythms"),
+          newHandle: findArtistHandlePDA(newName),
           systemProgram: accounts.systemProgram,
         })
         .signers([artist])
@@ -231,13 +303,44 @@ describe("soundmint", () => {
       expect(artistProfileAccount.socialLinks[0].platform).to.equal("Twitter");
       expect(artistProfileAccount.socialLinks[1].platform).to.equal("Instagram");
       expect(artistProfileAccount.socialLinks[2].platform).to.equal("SoundCloud");
+
+      // The rename moves the profile to the new handle and frees the old one
+      const newHandleAccount = await program.account.artistHandle.fetch(findArtistHandlePDA(newName));
+      const oldHandleAccount = await connection.getAccountInfo(findArtistHandlePDA("Cosmic Rhythms"));
+
+      expect(newHandleAccount.artistProfile.toString()).to.equal(artistProfilePDA.toString());
+      expect(oldHandleAccount).to.be.null;
     } catch (e) {
       console.error("Error updating artist profile:", e);
       throw e;
     }
   });
 
-  it("Admin can update treasury configuration", async () => {
+  it("Rejects an artist profile whose handle is already taken", async () => {
+    const impostorName = "cosmic-rhythms COLLECTIVE";
+    const [impostorProfilePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("artist_profile"), collaborator1.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .createArtistProfile(impostorName, "Not the real one", "https://soundmint.com/impostor.jpg", null)
+        .accounts({
+          authority: accounts.collaborator1,
+          artistProfile: impostorProfilePDA,
+          artistHandle: findArtistHandlePDA(impostorName),
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([collaborator1])
+        .rpc();
+      expect.fail("Duplicate artist handle should be rejected");
+    } catch (e) {
+      expect(e.toString()).to.include("HandleTaken");
+    }
+  });
+
+  it("Admin can schedule a treasury configuration change", async () => {
     try {
       // Update treasury config values
       const newMintFee = new BN(20000000); // 0.02 SOL
@@ -245,10 +348,11 @@ describe("soundmint", () => {
 
       // Update treasury configuration
       const txSignature = await program.methods
-        .updateTreasuryConfig(newMintFee, newPlatformFee, null)
+        .updateTreasuryConfig(newMintFee, newPlatformFee, null, null, null)
         .accounts({
           authority: accounts.authority,
           treasury: accounts.treasury,
+          role: null,
           systemProgram: accounts.systemProgram,
         })
         .signers([authority])
@@ -256,11 +360,14 @@ describe("soundmint", () => {
 
       await confirm(txSignature).then(log);
 
-      // Fetch and verify the updated treasury
+      // Fee changes are timelocked, so the current values stay in force for now
       const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
 
-      expect(treasuryAccount.mintFee.toString()).to.equal(newMintFee.toString());
-      expect(treasuryAccount.platformFeeBasisPoints).to.equal(newPlatformFee);
+      expect(treasuryAccount.mintFee.toString()).to.equal("10000000");
+      expect(treasuryAccount.platformFeeBasisPoints).to.equal(500);
+      expect(treasuryAccount.pendingConfig.mintFee.toString()).to.equal(newMintFee.toString());
+      expect(treasuryAccount.pendingConfig.platformFeeBasisPoints).to.equal(newPlatformFee);
+      expect(treasuryAccount.pendingConfig.effectiveAt.toNumber()).to.be.above(Date.now() / 1000);
     } catch (e) {
       console.error("Error updating treasury configuration:", e);
       throw e;
@@ -275,6 +382,7 @@ describe("soundmint", () => {
         .accounts({
           authority: accounts.authority,
           treasury: accounts.treasury,
+          role: null,
           systemProgram: accounts.systemProgram,
         })
         .signers([authority])
@@ -302,6 +410,7 @@ describe("soundmint", () => {
           artistProfile: accounts.artistProfile,
           artistOwner: accounts.artist,
           treasury: accounts.treasury,
+          role: null,
           systemProgram: accounts.systemProgram,
         })
         .signers([authority])
@@ -319,125 +428,2280 @@ describe("soundmint", () => {
     }
   });
 
-  // These tests can be enabled once NFT minting works
-
-  xit("Mints a master NFT", async () => {
+  it("Verifier role can verify artists without admin authority", async () => {
     try {
-      // Generate a new mint keypair for the NFT
-      const mintKeypair = Keypair.generate();
-
-      // Derive the master NFT PDA
-      const [masterNftPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("master_nft"), mintKeypair.publicKey.toBuffer()],
+      const verifier = Keypair.generate();
+      const [verifierRolePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("admin_role"), verifier.publicKey.toBuffer(), Buffer.from([0])],
         program.programId
       );
 
-      // Create metadata account address
-      const [metadataAccount] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("metadata"),
-          new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBuffer(),
-          mintKeypair.publicKey.toBuffer(),
-        ],
-        new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
+      const grantSignature = await program.methods
+        .grantRole({ verifier: {} })
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          holder: verifier.publicKey,
+          adminRole: verifierRolePDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(grantSignature).then(log);
+
+      const verifySignature = await program.methods
+        .verifyArtist(false)
+        .accounts({
+          authority: verifier.publicKey,
+          artistProfile: accounts.artistProfile,
+          artistOwner: accounts.artist,
+          treasury: accounts.treasury,
+          role: verifierRolePDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([verifier])
+        .rpc();
+
+      await confirm(verifySignature).then(log);
+
+      const artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      expect(artistProfileAccount.isVerified).to.be.false;
+
+      // A verifier cannot manage fees
+      try {
+        await program.methods
+          .updateTreasuryConfig(new BN(1), null, null, null, null)
+          .accounts({
+            authority: verifier.publicKey,
+            treasury: accounts.treasury,
+            role: verifierRolePDA,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([verifier])
+          .rpc();
+        expect.fail("Verifier should not be able to update fees");
+      } catch (e) {
+        expect(e.toString()).to.include("Unauthorized");
+      }
+
+      const revokeSignature = await program.methods
+        .revokeRole()
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          adminRole: verifierRolePDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(revokeSignature).then(log);
+
+      const roleAccount = await connection.getAccountInfo(verifierRolePDA);
+      expect(roleAccount).to.be.null;
+    } catch (e) {
+      console.error("Error using verifier role:", e);
+      throw e;
+    }
+  });
+
+  it("Guardian can pause and unpause instruction groups", async () => {
+    try {
+      const PAUSE_CLAIMS = 1 << 4;
+
+      const pauseSignature = await program.methods
+        .setPauseFlags(PAUSE_CLAIMS)
+        .accounts({
+          guardian: accounts.authority,
+          treasury: accounts.treasury,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(pauseSignature).then(log);
+
+      let treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.pausedFlags).to.equal(PAUSE_CLAIMS);
+
+      const unpauseSignature = await program.methods
+        .setPauseFlags(0)
+        .accounts({
+          guardian: accounts.authority,
+          treasury: accounts.treasury,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(unpauseSignature).then(log);
+
+      treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.pausedFlags).to.equal(0);
+    } catch (e) {
+      console.error("Error toggling pause flags:", e);
+      throw e;
+    }
+  });
+
+  it("Admin can propose and cancel a treasury authority handover", async () => {
+    try {
+      const newAuthority = Keypair.generate();
+
+      const proposeSignature = await program.methods
+        .proposeTreasuryAuthority(newAuthority.publicKey)
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(proposeSignature).then(log);
+
+      let treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.pendingAuthority.toString()).to.equal(newAuthority.publicKey.toString());
+
+      const cancelSignature = await program.methods
+        .cancelTreasuryAuthorityTransfer()
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(cancelSignature).then(log);
+
+      treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.pendingAuthority).to.be.null;
+      expect(treasuryAccount.authority.toString()).to.equal(authority.publicKey.toString());
+    } catch (e) {
+      console.error("Error handing over treasury authority:", e);
+      throw e;
+    }
+  });
+
+  it("Admin can schedule and remove a per-artist fee override", async () => {
+    try {
+      const [feeOverridePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_override"), artistProfilePDA.toBuffer()],
+        program.programId
       );
 
-      // Mint master NFT
-      const txSignature = await program.methods
-        .mintMasterNft(
-          masterNftTitle,
-          masterNftDescription,
-          audioUri,
-          artworkUri,
-          metadata
-        )
+      const setSignature = await program.methods
+        .setFeeOverride(new BN(0), 250, "Partner label rate")
         .accounts({
-          authority: accounts.artist,
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
           artistProfile: accounts.artistProfile,
-          masterNft: masterNftPDA,
-          mint: mintKeypair.publicKey,
-          // Add other required accounts once implementation is fixed
+          feeOverride: feeOverridePDA,
+          systemProgram: accounts.systemProgram,
         })
-        .signers([artist, mintKeypair])
+        .signers([authority])
         .rpc();
 
-      await confirm(txSignature).then(log);
+      await confirm(setSignature).then(log);
 
-      // Fetch the master NFT account
-      const masterNftAccount = await program.account.masterNft.fetch(masterNftPDA);
+      const feeOverrideAccount = await program.account.feeOverride.fetch(feeOverridePDA);
 
-      // Verify master NFT data
-      expect(masterNftAccount.title).to.equal(masterNftTitle);
-      expect(masterNftAccount.description).to.equal(masterNftDescription);
-      expect(masterNftAccount.audioUri).to.equal(audioUri);
-      expect(masterNftAccount.artworkUri).to.equal(artworkUri);
+      // The override is timelocked like treasury fee changes, so it starts out neutral
+      expect(feeOverrideAccount.artistProfile.toString()).to.equal(artistProfilePDA.toString());
+      expect(feeOverrideAccount.mintFee).to.be.null;
+      expect(feeOverrideAccount.platformFeeBasisPoints).to.be.null;
+      expect(feeOverrideAccount.pendingChange.mintFee.toString()).to.equal("0");
+      expect(feeOverrideAccount.pendingChange.platformFeeBasisPoints).to.equal(250);
+      expect(feeOverrideAccount.pendingChange.effectiveAt.toNumber()).to.be.above(Date.now() / 1000);
 
-      // Add this to our accounts for later use
-      accounts.masterNft = masterNftPDA;
-      accounts.masterNftMint = mintKeypair.publicKey;
+      // Removing an override that has not taken effect closes it right away
+
+      const removeSignature = await program.methods
+        .removeFeeOverride()
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          feeOverride: feeOverridePDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(removeSignature).then(log);
+
+      const closedAccount = await connection.getAccountInfo(feeOverridePDA);
+      expect(closedAccount).to.be.null;
     } catch (e) {
-      console.error("Error minting master NFT:", e);
+      console.error("Error managing fee override:", e);
       throw e;
     }
   });
 
-  xit("Creates a royalty split", async () => {
+  it("Admin can issue and revoke a fee waiver voucher", async () => {
     try {
-      // Derive the royalty split PDA
-      const [royaltySplitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("royalty_split"), accounts.masterNft.toBuffer()],
+      const [feeVoucherPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_voucher"), artistProfilePDA.toBuffer()],
         program.programId
       );
+      const expiresAt = new BN(Math.floor(Date.now() / 1000) + 30 * 24 * 60 * 60);
 
-      // Configure royalty split
-      const collaborators = [
-        {
-          address: accounts.artist,
-          shareBasisPoints: 7000, // 70%
-          name: "Main Artist"
-        },
-        {
-          address: accounts.collaborator1,
-          shareBasisPoints: 2000, // 20%
-          name: "Producer"
-        },
-        {
-          address: accounts.collaborator2,
-          shareBasisPoints: 1000, // 10%
-          name: "Featured Artist"
+      const issue = (uses: number, expiry: BN) =>
+        program.methods
+          .issueFeeVoucher(uses, expiry)
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            role: null,
+            artistProfile: accounts.artistProfile,
+            feeVoucher: feeVoucherPDA,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+
+      // A single issuance can't hand out an open-ended waiver
+      for (const [uses, expiry] of [
+        [26, expiresAt],
+        [3, new BN(Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60)],
+      ] as [number, BN][]) {
+        try {
+          await issue(uses, expiry);
+          expect.fail("Voucher beyond the issuance limit should be rejected");
+        } catch (e) {
+          expect(e.error.errorCode.code).to.equal("FeeVoucherLimitExceeded");
         }
-      ];
+      }
 
-      // Create royalty split
+      const issueSignature = await issue(3, expiresAt);
+
+      await confirm(issueSignature).then(log);
+
+      const feeVoucherAccount = await program.account.feeVoucher.fetch(feeVoucherPDA);
+
+      expect(feeVoucherAccount.beneficiary.toString()).to.equal(artistProfilePDA.toString());
+      expect(feeVoucherAccount.remainingUses).to.equal(3);
+      expect(feeVoucherAccount.redeemedCount).to.equal(0);
+      expect(feeVoucherAccount.expiresAt.toString()).to.equal(expiresAt.toString());
+
+      const revokeSignature = await program.methods
+        .revokeFeeVoucher()
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          feeVoucher: feeVoucherPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(revokeSignature).then(log);
+
+      const closedAccount = await connection.getAccountInfo(feeVoucherPDA);
+      expect(closedAccount).to.be.null;
+    } catch (e) {
+      console.error("Error managing fee voucher:", e);
+      throw e;
+    }
+  });
+
+  it("Admin can schedule the referral share of mint fees", async () => {
+    try {
       const txSignature = await program.methods
-        .createRoyaltySplit(collaborators)
+        .setReferralFee(1000) // 10% of each mint fee
         .accounts({
-          authority: accounts.artist,
-          artistProfile: accounts.artistProfile,
-          masterNft: accounts.masterNft,
-          royaltySplit: royaltySplitPDA,
-          systemProgram: accounts.systemProgram
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          systemProgram: accounts.systemProgram,
         })
-        .signers([artist])
+        .signers([authority])
         .rpc();
 
       await confirm(txSignature).then(log);
 
-      // Fetch the royalty split account
-      const royaltySplitAccount = await program.account.royaltySplit.fetch(royaltySplitPDA);
+      const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      const artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
 
-      // Verify royalty split data
-      expect(royaltySplitAccount.masterNft.toString()).to.equal(accounts.masterNft.toString());
-      expect(royaltySplitAccount.collaborators).to.have.lengthOf(3);
-      expect(royaltySplitAccount.totalBasisPoints).to.equal(10000);
-      expect(royaltySplitAccount.collaborators[0].shareBasisPoints).to.equal(7000);
-      expect(royaltySplitAccount.collaborators[1].shareBasisPoints).to.equal(2000);
-      expect(royaltySplitAccount.collaborators[2].shareBasisPoints).to.equal(1000);
+      // Referral changes wait out the same timelock as the other fee settings
+      expect(treasuryAccount.referralFeeBasisPoints).to.equal(0);
+      expect(treasuryAccount.pendingConfig.referralFeeBasisPoints).to.equal(1000);
+      expect(treasuryAccount.totalReferralRewardsPaid.toString()).to.equal("0");
+      expect(artistProfileAccount.referrer).to.be.null;
+    } catch (e) {
+      console.error("Error configuring referral fee:", e);
+      throw e;
+    }
+  });
 
-      // Add to our accounts
-      accounts.royaltySplit = royaltySplitPDA;
+  it("Closes an artist profile with no tracks and frees its handle", async () => {
+    try {
+      const name = "Short Lived Project";
+      const [profilePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("artist_profile"), collaborator2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const createSignature = await program.methods
+        .createArtistProfile(name, "Temporary profile", "https://soundmint.com/artists/short-lived.jpg", null)
+        .accounts({
+          authority: accounts.collaborator2,
+          artistProfile: profilePDA,
+          artistHandle: findArtistHandlePDA(name),
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([collaborator2])
+        .rpc();
+
+      await confirm(createSignature).then(log);
+
+      const closeSignature = await program.methods
+        .closeArtistProfile()
+        .accounts({
+          authority: accounts.collaborator2,
+          artistProfile: profilePDA,
+          artistHandle: findArtistHandlePDA(name),
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([collaborator2])
+        .rpc();
+
+      await confirm(closeSignature).then(log);
+
+      expect(await connection.getAccountInfo(profilePDA)).to.be.null;
+      expect(await connection.getAccountInfo(findArtistHandlePDA(name))).to.be.null;
     } catch (e) {
-      console.error("Error creating royalty split:", e);
+      console.error("Error closing artist profile:", e);
+      throw e;
+    }
+  });
+
+  it("Artist can hand their profile to a new wallet and back", async () => {
+    try {
+      const newOwner = Keypair.generate();
+
+      const handOver = async (from: Keypair, to: Keypair) => {
+        const proposeSignature = await program.methods
+          .proposeArtistProfileTransfer(to.publicKey)
+          .accounts({
+            authority: from.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([from])
+          .rpc();
+
+        await confirm(proposeSignature).then(log);
+
+        const acceptSignature = await program.methods
+          .acceptArtistProfileTransfer()
+          .accounts({
+            newAuthority: to.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([to])
+          .rpc();
+
+        await confirm(acceptSignature).then(log);
+      };
+
+      await handOver(artist, newOwner);
+
+      // The profile keeps its address, so the catalog stays linked to it
+      let artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      expect(artistProfileAccount.authority.toString()).to.equal(newOwner.publicKey.toString());
+      expect(artistProfileAccount.creator.toString()).to.equal(artist.publicKey.toString());
+      expect(artistProfileAccount.pendingAuthority).to.be.null;
+
+      await handOver(newOwner, artist);
+
+      artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      expect(artistProfileAccount.authority.toString()).to.equal(artist.publicKey.toString());
+    } catch (e) {
+      console.error("Error transferring artist profile:", e);
+      throw e;
+    }
+  });
+
+  it("Artist can grant and remove a scoped delegate", async () => {
+    try {
+      const [artistDelegatePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("artist_delegate"), artistProfilePDA.toBuffer(), collaborator1.publicKey.toBuffer()],
+        program.programId
+      );
+      const mintTracks = 1 << 0;
+      const updateMetadata = 1 << 1;
+
+      const setSignature = await program.methods
+        .setArtistDelegate(mintTracks | updateMetadata)
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          delegate: accounts.collaborator1,
+          artistDelegate: artistDelegatePDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(setSignature).then(log);
+
+      const artistDelegateAccount = await program.account.artistDelegate.fetch(artistDelegatePDA);
+
+      expect(artistDelegateAccount.artistProfile.toString()).to.equal(artistProfilePDA.toString());
+      expect(artistDelegateAccount.delegate.toString()).to.equal(collaborator1.publicKey.toString());
+      expect(artistDelegateAccount.permissions).to.equal(mintTracks | updateMetadata);
+
+      let artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      expect(artistDelegateAccount.generation).to.equal(artistProfileAccount.delegateGeneration);
+
+      // Handing the profile over retires every delegate the previous owner appointed
+      const newOwner = Keypair.generate();
+      for (const [from, to] of [
+        [artist, newOwner],
+        [newOwner, artist],
+      ]) {
+        await program.methods
+          .proposeArtistProfileTransfer(to.publicKey)
+          .accounts({
+            authority: from.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([from])
+          .rpc()
+          .then(confirm);
+        await program.methods
+          .acceptArtistProfileTransfer()
+          .accounts({
+            newAuthority: to.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([to])
+          .rpc()
+          .then(confirm);
+      }
+
+      artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      expect(artistProfileAccount.delegateGeneration).to.equal(artistDelegateAccount.generation + 2);
+
+      const removeSignature = await program.methods
+        .removeArtistDelegate()
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          artistDelegate: artistDelegatePDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(removeSignature).then(log);
+
+      expect(await connection.getAccountInfo(artistDelegatePDA)).to.be.null;
+    } catch (e) {
+      console.error("Error managing artist delegate:", e);
+      throw e;
+    }
+  });
+
+  it("Label can sign an artist to its roster and release them", async () => {
+    try {
+      const labelOwner = collaborator2;
+      const [labelPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("label"), labelOwner.publicKey.toBuffer()],
+        program.programId
+      );
+      const [membershipPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("label_membership"), labelPDA.toBuffer(), artistProfilePDA.toBuffer()],
+        program.programId
+      );
+
+      const createSignature = await program.methods
+        .createLabel("Nebula Records", labelOwner.publicKey, 1500)
+        .accounts({
+          authority: labelOwner.publicKey,
+          label: labelPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([labelOwner])
+        .rpc();
+
+      await confirm(createSignature).then(log);
+
+      const inviteSignature = await program.methods
+        .inviteArtistToLabel()
+        .accounts({
+          authority: labelOwner.publicKey,
+          label: labelPDA,
+          artistProfile: accounts.artistProfile,
+          membership: membershipPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([labelOwner])
+        .rpc();
+
+      await confirm(inviteSignature).then(log);
+
+      // Joining only takes effect once the artist accepts
+      let artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      expect(artistProfileAccount.label).to.be.null;
+
+      const acceptSignature = await program.methods
+        .acceptLabelInvite()
+        .accounts({
+          authority: accounts.artist,
+          label: labelPDA,
+          artistProfile: accounts.artistProfile,
+          membership: membershipPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(acceptSignature).then(log);
+
+      artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      let labelAccount = await program.account.label.fetch(labelPDA);
+      const membershipAccount = await program.account.labelMembership.fetch(membershipPDA);
+
+      expect(artistProfileAccount.label.toString()).to.equal(labelPDA.toString());
+      expect(labelAccount.artistCount).to.equal(1);
+      expect(labelAccount.defaultShareBasisPoints).to.equal(1500);
+      expect(membershipAccount.status).to.deep.equal({ active: {} });
+
+      const endSignature = await program.methods
+        .endLabelMembership()
+        .accounts({
+          authority: accounts.artist,
+          label: labelPDA,
+          artistProfile: accounts.artistProfile,
+          membership: membershipPDA,
+          invitedBy: labelOwner.publicKey,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(endSignature).then(log);
+
+      artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+      labelAccount = await program.account.label.fetch(labelPDA);
+
+      expect(artistProfileAccount.label).to.be.null;
+      expect(labelAccount.artistCount).to.equal(0);
+      expect(await connection.getAccountInfo(membershipPDA)).to.be.null;
+    } catch (e) {
+      console.error("Error managing label roster:", e);
+      throw e;
+    }
+  });
+
+  it("Artist can request verification and an admin can review it", async () => {
+    try {
+      const [verificationRequestPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("verification_request"), artistProfilePDA.toBuffer()],
+        program.programId
+      );
+
+      const review = async (approve: boolean, tier: object | null, reasonCode: number) => {
+        const reviewSignature = await program.methods
+          .reviewVerificationRequest(approve, tier as any, reasonCode)
+          .accounts({
+            authority: accounts.authority,
+            artistProfile: accounts.artistProfile,
+            verificationRequest: verificationRequestPDA,
+            treasury: accounts.treasury,
+            role: null,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+
+        await confirm(reviewSignature).then(log);
+      };
+
+      const submit = async (tier: object) => {
+        const submitSignature = await program.methods
+          .submitVerificationRequest("https://soundmint.com/press/artist-kit", tier as any)
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            verificationRequest: verificationRequestPDA,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([artist])
+          .rpc();
+
+        await confirm(submitSignature).then(log);
+      };
+
+      // An upgrade to the top tier is turned down, then a lesser tier is granted on resubmission
+      await submit({ official: {} });
+      await review(false, null, 3);
+
+      let verificationRequestAccount = await program.account.verificationRequest.fetch(verificationRequestPDA);
+      expect(verificationRequestAccount.status).to.deep.equal({ rejected: {} });
+
+      await submit({ official: {} });
+      await review(true, { notable: {} }, 0);
+
+      verificationRequestAccount = await program.account.verificationRequest.fetch(verificationRequestPDA);
+      const artistProfileAccount = await program.account.artistProfile.fetch(artistProfilePDA);
+
+      expect(verificationRequestAccount.status).to.deep.equal({ approved: {} });
+      expect(verificationRequestAccount.history.length).to.equal(2);
+      expect(verificationRequestAccount.history[0].reasonCode).to.equal(3);
+      expect(artistProfileAccount.isVerified).to.be.true;
+      expect(artistProfileAccount.verificationTier).to.deep.equal({ notable: {} });
+      expect(artistProfileAccount.verifiedAt.toNumber()).to.be.greaterThan(0);
+    } catch (e) {
+      console.error("Error reviewing verification request:", e);
+      throw e;
+    }
+  });
+
+  it("A reserved handle stays with the profile after it changes wallets", async () => {
+    try {
+      const newOwner = Keypair.generate();
+      const currentName = "Cosmic Rhythms Collective";
+      const reservedName = "Cosmic Nova";
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.publicKey,
+            toPubkey: newOwner.publicKey,
+            lamports: LAMPORTS_PER_SOL,
+          })
+        ) as any
+      );
+
+      await program.methods
+        .reserveArtistHandle(reservedName)
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          artistProfile: accounts.artistProfile,
+          artistHandle: findArtistHandlePDA(reservedName),
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc()
+        .then(confirm);
+
+      const handOver = async (from: Keypair, to: Keypair) => {
+        await program.methods
+          .proposeArtistProfileTransfer(to.publicKey)
+          .accounts({
+            authority: from.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([from])
+          .rpc()
+          .then(confirm);
+        await program.methods
+          .acceptArtistProfileTransfer()
+          .accounts({
+            newAuthority: to.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([to])
+          .rpc()
+          .then(confirm);
+      };
+
+      const rename = (owner: Keypair, from: string, to: string) =>
+        program.methods
+          .updateArtistProfile(to, null, null, null)
+          .accounts({
+            authority: owner.publicKey,
+            artistProfile: accounts.artistProfile,
+            currentHandle: findArtistHandlePDA(from),
+            newHandle: findArtistHandlePDA(to),
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([owner])
+          .rpc()
+          .then(confirm);
+
+      // The reservation is held for the profile, so the new wallet can still claim it
+      await handOver(artist, newOwner);
+      await rename(newOwner, currentName, reservedName);
+
+      let reservedHandle = await program.account.artistHandle.fetch(findArtistHandlePDA(reservedName));
+      expect(reservedHandle.artistProfile.toString()).to.equal(artistProfilePDA.toString());
+      expect(reservedHandle.reservedFor.toString()).to.equal(artistProfilePDA.toString());
+
+      // Moving off the reserved handle leaves it reserved rather than closing it
+      await rename(newOwner, reservedName, currentName);
+      await handOver(newOwner, artist);
+
+      reservedHandle = await program.account.artistHandle.fetch(findArtistHandlePDA(reservedName));
+      expect(reservedHandle.artistProfile.toString()).to.equal(PublicKey.default.toString());
+      expect(reservedHandle.reservedFor.toString()).to.equal(artistProfilePDA.toString());
+    } catch (e) {
+      console.error("Error reserving artist handle:", e);
+      throw e;
+    }
+  });
+
+  it("Mints a master NFT", async () => {
+    try {
+      // Generate a new mint keypair for the NFT
+      const mintKeypair = Keypair.generate();
+
+      // Derive the master NFT PDA
+      const [masterNftPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("master_nft"), mintKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Create metadata account address
+      const [metadataAccount] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBuffer(),
+          mintKeypair.publicKey.toBuffer(),
+        ],
+        new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
+      );
+
+      // Mint master NFT
+      const txSignature = await program.methods
+        .mintMasterNft(
+          masterNftTitle,
+          masterNftDescription,
+          audioUri,
+          artworkUri,
+          audioHash,
+          artworkHash,
+          audioSpec as any,
+          metadata,
+          isrc,
+          fingerprint,
+          null
+        )
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          masterNft: masterNftPDA,
+          delegate: null,
+          label: null,
+          artistWallet: accounts.artist,
+          mint: mintKeypair.publicKey,
+          feeVoucher: null,
+          referrer: null,
+          isrcRegistration: findIdentifierPDA(program.programId, IDENTIFIER_KIND.isrc, isrc),
+          audioFingerprint: findAudioFingerprintPDA(fingerprint),
+        })
+        .signers([artist, mintKeypair])
+        .rpc();
+
+      await confirm(txSignature).then(log);
+
+      // Fetch the master NFT account
+      const masterNftAccount = await program.account.masterNft.fetch(masterNftPDA);
+
+      // Verify master NFT data
+      expect(masterNftAccount.title).to.equal(masterNftTitle);
+      expect(masterNftAccount.description).to.equal(masterNftDescription);
+      expect(masterNftAccount.audioUri).to.equal(audioUri);
+      expect(masterNftAccount.artworkUri).to.equal(artworkUri);
+      expect(masterNftAccount.audioHash).to.deep.equal(audioHash);
+      expect(masterNftAccount.audioSpec.sampleRateHz).to.equal(44100);
+      expect(masterNftAccount.isrc).to.equal(isrc);
+      expect((await lookupMasterNftByIsrc(program, "US-S1Z-99-00001")).toString()).to.equal(masterNftPDA.toString());
+      expect(masterNftAccount.version).to.be.null;
+
+      const audioFingerprint = await program.account.audioFingerprint.fetch(findAudioFingerprintPDA(fingerprint));
+      expect(audioFingerprint.original.toString()).to.equal(masterNftPDA.toString());
+      expect(audioFingerprint.versionCount).to.equal(0);
+
+      // The program PDA holds mint authority, not the artist, so no second token can be minted
+      const mintInfo = await readMint(mintKeypair.publicKey);
+      expect(mintInfo.mintAuthority.toString()).to.equal(nftAuthorityPDA.toString());
+      expect(mintInfo.supply.toNumber()).to.equal(1);
+
+      // Add this to our accounts for later use
+      accounts.masterNft = masterNftPDA;
+      accounts.masterNftMint = mintKeypair.publicKey;
+    } catch (e) {
+      console.error("Error minting master NFT:", e);
+      throw e;
+    }
+  });
+
+  it("Mint fees accumulate in the treasury PDA and are withdrawn per category", async () => {
+    try {
+      let treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      const available = treasuryAccount.mintFees.collected.sub(treasuryAccount.mintFees.withdrawn);
+      expect(available.toNumber()).to.be.above(0);
+
+      // Fees sit in the treasury PDA itself until a withdrawal
+      const treasuryBalance = await connection.getBalance(treasuryPDA);
+      const rentExemptMinimum = await connection.getMinimumBalanceForRentExemption(
+        (await connection.getAccountInfo(treasuryPDA)).data.length
+      );
+      expect(treasuryBalance - rentExemptMinimum).to.be.at.least(available.toNumber());
+
+      const withdraw = (category: object, amount: BN) =>
+        program.methods
+          .withdrawTreasuryFunds(category as any, amount)
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            role: null,
+            treasuryWallet: treasuryAccount.treasuryWallet,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+
+      // Other categories can't dip into mint fees
+      try {
+        await withdraw({ streaming: {} }, available);
+        expect.fail("Streaming fees have not been collected");
+      } catch (e) {
+        expect(e.toString()).to.include("InsufficientFunds");
+      }
+
+      const walletBalance = await connection.getBalance(treasuryAccount.treasuryWallet);
+      await withdraw({ mint: {} }, available).then(confirm).then(log);
+
+      expect(await connection.getBalance(treasuryAccount.treasuryWallet)).to.equal(
+        walletBalance + available.toNumber()
+      );
+      expect(await connection.getBalance(treasuryPDA)).to.equal(treasuryBalance - available.toNumber());
+      treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+      expect(treasuryAccount.mintFees.withdrawn.toString()).to.equal(treasuryAccount.mintFees.collected.toString());
+
+      // Nothing further can leave once the ledger is drawn down
+      try {
+        await withdraw({ mint: {} }, new BN(1));
+        expect.fail("Mint fees are already withdrawn");
+      } catch (e) {
+        expect(e.toString()).to.include("InsufficientFunds");
+      }
+    } catch (e) {
+      console.error("Error withdrawing treasury fees:", e);
+      throw e;
+    }
+  });
+
+  it("A collection stays with the artist profile across a wallet transfer", async () => {
+    try {
+      const newOwner = Keypair.generate();
+      const collectionMint = Keypair.generate();
+      const [collectionPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("sound_mint_collection"), collectionMint.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.publicKey,
+            toPubkey: newOwner.publicKey,
+            lamports: LAMPORTS_PER_SOL,
+          })
+        ) as any
+      );
+
+      await program.methods
+        .createCollection("Cosmic Journey EP", "Debut release", "https://soundmint.com/collections/cosmic-journey.json", null)
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          delegate: null,
+          label: null,
+          artistWallet: accounts.artist,
+          collection: collectionPDA,
+          mint: collectionMint.publicKey,
+          tokenAccount: anchor.utils.token.associatedAddress({
+            mint: collectionMint.publicKey,
+            owner: accounts.artist,
+          }),
+          treasury: accounts.treasury,
+          upcRegistration: null,
+        })
+        .signers([artist, collectionMint])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const handOver = async (from: Keypair, to: Keypair) => {
+        await program.methods
+          .proposeArtistProfileTransfer(to.publicKey)
+          .accounts({
+            authority: from.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([from])
+          .rpc()
+          .then(confirm);
+        await program.methods
+          .acceptArtistProfileTransfer()
+          .accounts({
+            newAuthority: to.publicKey,
+            artistProfile: accounts.artistProfile,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([to])
+          .rpc()
+          .then(confirm);
+      };
+
+      // Authority comes from the profile, so the new wallet manages the collection right away
+      await handOver(artist, newOwner);
+      await program.methods
+        .addToCollection()
+        .accounts({
+          authority: newOwner.publicKey,
+          artistProfile: accounts.artistProfile,
+          delegate: null,
+          label: null,
+          collection: collectionPDA,
+          masterNft: accounts.masterNft,
+          treasury: accounts.treasury,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([newOwner])
+        .rpc()
+        .then(confirm)
+        .then(log);
+      await handOver(newOwner, artist);
+
+      const collection = await program.account.collection.fetch(collectionPDA);
+      expect(collection.artistProfile.toString()).to.equal(artistProfilePDA.toString());
+      expect(collection.nftCount.toNumber()).to.equal(1);
+    } catch (e) {
+      console.error("Error managing collection:", e);
+      throw e;
+    }
+  });
+
+  it("A UPC-A and its EAN-13 spelling register as the same release", async () => {
+    try {
+      const upcA = "036000291452";
+      const ean13 = "0036000291452";
+      const registrationPDA = findIdentifierPDA(program.programId, IDENTIFIER_KIND.upc, ean13);
+
+      const createCollection = async (upc: string) => {
+        const collectionMint = Keypair.generate();
+        const [collectionPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("sound_mint_collection"), collectionMint.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .createCollection("Cosmic Journey Deluxe", "Deluxe release", "https://soundmint.com/collections/deluxe.json", upc)
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            delegate: null,
+            label: null,
+            artistWallet: accounts.artist,
+            collection: collectionPDA,
+            mint: collectionMint.publicKey,
+            tokenAccount: anchor.utils.token.associatedAddress({
+              mint: collectionMint.publicKey,
+              owner: accounts.artist,
+            }),
+            treasury: accounts.treasury,
+            upcRegistration: registrationPDA,
+          })
+          .signers([artist, collectionMint])
+          .rpc()
+          .then(confirm);
+        return collectionPDA;
+      };
+
+      // The 12-digit form is stored and seeded as EAN-13
+      const collectionPDA = await createCollection(upcA);
+      const registration = await program.account.identifierRegistration.fetch(registrationPDA);
+      expect(registration.code).to.equal(ean13);
+      expect((await program.account.collection.fetch(collectionPDA)).upc).to.equal(ean13);
+      expect((await lookupCollectionByUpc(program, upcA)).toString()).to.equal(collectionPDA.toString());
+      expect((await lookupCollectionByUpc(program, ean13)).toString()).to.equal(collectionPDA.toString());
+
+      // so the same barcode can't be claimed again under its other spelling
+      try {
+        await createCollection(ean13);
+        expect.fail("Second registration of the same UPC should fail");
+      } catch (e) {
+        expect(e.toString()).to.not.include("Second registration");
+      }
+
+      // A malformed code longer than a seed still reaches validation
+      const malformed = "0036000291452-DELUXE-EDITION-REISSUE";
+      const collectionMint = Keypair.generate();
+      try {
+        await program.methods
+          .createCollection("Cosmic Journey Reissue", "Reissue", "https://soundmint.com/collections/reissue.json", malformed)
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            delegate: null,
+            label: null,
+            artistWallet: accounts.artist,
+            collection: PublicKey.findProgramAddressSync(
+              [Buffer.from("sound_mint_collection"), collectionMint.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            mint: collectionMint.publicKey,
+            tokenAccount: anchor.utils.token.associatedAddress({
+              mint: collectionMint.publicKey,
+              owner: accounts.artist,
+            }),
+            treasury: accounts.treasury,
+            upcRegistration: findIdentifierPDA(program.programId, IDENTIFIER_KIND.upc, canonicalUpc(malformed)),
+          })
+          .signers([artist, collectionMint])
+          .rpc();
+        expect.fail("A malformed UPC should be rejected");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("InvalidIdentifier");
+      }
+    } catch (e) {
+      console.error("Error registering UPC:", e);
+      throw e;
+    }
+  });
+
+  it("Creates a royalty split", async () => {
+    try {
+      // Derive the royalty split PDA
+      const [royaltySplitPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("royalty_split"), accounts.masterNft.toBuffer()],
+        program.programId
+      );
+
+      // Configure royalty split
+      const collaborators = [
+        {
+          address: accounts.artist,
+          shareBasisPoints: 7000, // 70%
+          name: "Main Artist"
+        },
+        {
+          address: accounts.collaborator1,
+          shareBasisPoints: 2000, // 20%
+          name: "Producer"
+        },
+        {
+          address: accounts.collaborator2,
+          shareBasisPoints: 1000, // 10%
+          name: "Featured Artist"
+        }
+      ];
+
+      // Create royalty split
+      const txSignature = await program.methods
+        .createRoyaltySplit(collaborators)
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          delegate: null,
+          label: null,
+          masterNft: accounts.masterNft,
+          royaltySplit: royaltySplitPDA,
+          systemProgram: accounts.systemProgram
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(txSignature).then(log);
+
+      // Fetch the royalty split account
+      const royaltySplitAccount = await program.account.royaltySplit.fetch(royaltySplitPDA);
+
+      // Verify royalty split data
+      expect(royaltySplitAccount.masterNft.toString()).to.equal(accounts.masterNft.toString());
+      expect(royaltySplitAccount.collaborators).to.have.lengthOf(3);
+      expect(royaltySplitAccount.totalBasisPoints).to.equal(10000);
+      expect(royaltySplitAccount.collaborators[0].shareBasisPoints).to.equal(7000);
+      expect(royaltySplitAccount.collaborators[1].shareBasisPoints).to.equal(2000);
+      expect(royaltySplitAccount.collaborators[2].shareBasisPoints).to.equal(1000);
+
+      // Add to our accounts
+      accounts.royaltySplit = royaltySplitPDA;
+    } catch (e) {
+      console.error("Error creating royalty split:", e);
+      throw e;
+    }
+  });
+
+  it("Links a remix to its parent with an upstream share", async () => {
+    // Mint the remix as its own recording
+    const remixMint = Keypair.generate();
+    const remixFingerprint = Array.from(createHash("sha256").update("cosmic-journey-remix-fingerprint").digest());
+    const [remixPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("master_nft"), remixMint.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .mintMasterNft(
+        "Cosmic Journey (Club Remix)",
+        masterNftDescription,
+        audioUri,
+        artworkUri,
+        audioHash,
+        artworkHash,
+        audioSpec as any,
+        metadata,
+        null,
+        remixFingerprint,
+        null
+      )
+      .accounts({
+        authority: accounts.artist,
+        artistProfile: accounts.artistProfile,
+        delegate: null,
+        label: null,
+        artistWallet: accounts.artist,
+        masterNft: remixPDA,
+        mint: remixMint.publicKey,
+        feeVoucher: null,
+        referrer: null,
+        isrcRegistration: null,
+        audioFingerprint: findAudioFingerprintPDA(remixFingerprint),
+      })
+      .signers([artist, remixMint])
+      .rpc()
+      .then(confirm);
+
+    const [remixSplitPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("royalty_split"), remixPDA.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .createRoyaltySplit([{ address: accounts.artist, shareBasisPoints: 10000, name: "Remixer" }])
+      .accounts({
+        authority: accounts.artist,
+        artistProfile: accounts.artistProfile,
+        delegate: null,
+        label: null,
+        masterNft: remixPDA,
+        royaltySplit: remixSplitPDA,
+      })
+      .signers([artist])
+      .rpc()
+      .then(confirm);
+
+    const [derivativeLinkPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("derivative_link"), remixPDA.toBuffer(), accounts.masterNft.toBuffer()],
+      program.programId
+    );
+
+    // Upstream shares are capped at half of the derivative's revenue
+    try {
+      await program.methods
+        .declareDerivative({ remix: {} }, 6000)
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          delegate: null,
+          label: null,
+          masterNft: remixPDA,
+          parentMasterNft: accounts.masterNft,
+          derivativeLink: derivativeLinkPDA,
+        })
+        .signers([artist])
+        .rpc();
+      expect.fail("Upstream share above the cap should be rejected");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("UpstreamShareTooHigh");
+    }
+
+    await program.methods
+      .declareDerivative({ remix: {} }, 2500)
+      .accounts({
+        authority: accounts.artist,
+        artistProfile: accounts.artistProfile,
+        delegate: null,
+        label: null,
+        masterNft: remixPDA,
+        parentMasterNft: accounts.masterNft,
+        derivativeLink: derivativeLinkPDA,
+      })
+      .signers([artist])
+      .rpc()
+      .then(confirm);
+
+    // The parent's artist agrees, which wires the share into the remix's split
+    await program.methods
+      .acceptDerivative()
+      .accounts({
+        authority: accounts.artist,
+        artistProfile: accounts.artistProfile,
+        delegate: null,
+        label: null,
+        parentMasterNft: accounts.masterNft,
+        derivativeLink: derivativeLinkPDA,
+        derivativeRoyaltySplit: remixSplitPDA,
+      })
+      .signers([artist])
+      .rpc()
+      .then(confirm);
+
+    const derivativeLink = await program.account.derivativeLink.fetch(derivativeLinkPDA);
+    expect(derivativeLink.status).to.deep.equal({ accepted: {} });
+
+    const remixSplit = await program.account.royaltySplit.fetch(remixSplitPDA);
+    expect(remixSplit.upstream).to.have.lengthOf(1);
+    expect(remixSplit.upstream[0].parent.toString()).to.equal(accounts.masterNft.toString());
+    expect(remixSplit.upstream[0].basisPoints).to.equal(2500);
+
+    accounts.remix = remixPDA;
+    accounts.remixSplit = remixSplitPDA;
+  });
+
+  it("Creates Metaplex metadata for the master NFT", async () => {
+    try {
+      const tokenMetadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+      const [metadataAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), tokenMetadataProgram.toBuffer(), accounts.masterNftMint.toBuffer()],
+        tokenMetadataProgram
+      );
+      const metadataUri = "https://soundmint.com/metadata/cosmic-journey.json";
+
+      const txSignature = await program.methods
+        .createNftMetadata(metadataUri, 750)
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          delegate: null,
+          label: null,
+          masterNft: accounts.masterNft,
+          royaltySplit: accounts.royaltySplit,
+          mint: accounts.masterNftMint,
+          metadataAccount,
+          tokenMetadataProgram,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(txSignature).then(log);
+
+      // Creators and their shares are read straight from the royalty split
+      const masterNftAccount = await program.account.masterNft.fetch(accounts.masterNft);
+      expect(masterNftAccount.metadataUri).to.equal(metadataUri);
+      expect(await connection.getAccountInfo(metadataAccount)).to.not.be.null;
+
+      // The token metadata URI carries a digest of the hashes and audio spec the track was minted with
+      const expectedUri = boundMetadataUri(metadataUri, audioHash, artworkHash, audioSpec);
+      expect(await readTokenMetadataUri(metadataAccount)).to.equal(expectedUri);
+
+      // and the off-chain JSON carries the same content, so the digest can be checked against it
+      const json = buildTrackMetadata({
+        title: masterNftTitle,
+        description: masterNftDescription,
+        audioUri,
+        artworkUri,
+        audioHash,
+        artworkHash,
+        audioSpec,
+      });
+      expect(expectedUri.endsWith(`#content=${json.properties.content_digest}`)).to.be.true;
+      expect(json.properties.files[0].sha256).to.equal(Buffer.from(audioHash).toString("hex"));
+
+      // Mint authority is revoked once metadata exists, fixing supply at 1
+      const mintInfo = await readMint(accounts.masterNftMint);
+      expect(mintInfo.mintAuthority).to.be.null;
+      expect(mintInfo.supply.toNumber()).to.equal(1);
+    } catch (e) {
+      console.error("Error creating master NFT metadata:", e);
+      throw e;
+    }
+  });
+
+  it("Syncs master NFT updates to its token metadata", async () => {
+    try {
+      const tokenMetadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+      const [metadataAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), tokenMetadataProgram.toBuffer(), accounts.masterNftMint.toBuffer()],
+        tokenMetadataProgram
+      );
+      const metadataUri = "https://soundmint.com/metadata/cosmic-journey-v2.json";
+
+      // The program PDA is the update authority, so the artist goes through the program
+      const txSignature = await program.methods
+        .updateMasterNft("A remastered electronic journey", null, null, null, metadataUri)
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          delegate: null,
+          label: null,
+          masterNft: accounts.masterNft,
+          metadataAccount,
+          mint: accounts.masterNftMint,
+          tokenAccount: null,
+          tokenMetadataProgram,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc();
+
+      await confirm(txSignature).then(log);
+
+      const masterNftAccount = await program.account.masterNft.fetch(accounts.masterNft);
+      expect(masterNftAccount.metadataUri).to.equal(metadataUri);
+      expect(await readTokenMetadataUri(metadataAccount)).to.equal(
+        boundMetadataUri(metadataUri, audioHash, artworkHash, audioSpec)
+      );
+    } catch (e) {
+      console.error("Error syncing master NFT metadata:", e);
+      throw e;
+    }
+  });
+
+  it("Freezes the master NFT token while it is non-transferable", async () => {
+    try {
+      const tokenMetadataProgram = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+      const [metadataAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), tokenMetadataProgram.toBuffer(), accounts.masterNftMint.toBuffer()],
+        tokenMetadataProgram
+      );
+      const tokenAccount = anchor.utils.token.associatedAddress({
+        mint: accounts.masterNftMint,
+        owner: accounts.artist,
+      });
+
+      const setTransferable = async (isTransferable: boolean, metadataUri: string) => {
+        const txSignature = await program.methods
+          .updateMasterNft(null, null, isTransferable, null, metadataUri)
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            delegate: null,
+            label: null,
+            masterNft: accounts.masterNft,
+            metadataAccount,
+            mint: accounts.masterNftMint,
+            tokenAccount,
+            tokenMetadataProgram,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([artist])
+          .rpc();
+
+        await confirm(txSignature).then(log);
+      };
+
+      // Token account state byte: 1 = initialized, 2 = frozen
+      await setTransferable(false, "https://soundmint.com/metadata/cosmic-journey-v3.json");
+      let tokenAccountInfo = await connection.getAccountInfo(tokenAccount);
+      expect(tokenAccountInfo.data[108]).to.equal(2);
+
+      await setTransferable(true, "https://soundmint.com/metadata/cosmic-journey-v4.json");
+      tokenAccountInfo = await connection.getAccountInfo(tokenAccount);
+      expect(tokenAccountInfo.data[108]).to.equal(1);
+    } catch (e) {
+      console.error("Error freezing master NFT token:", e);
+      throw e;
+    }
+  });
+
+  it("Moderator can take down a master NFT and lift it again", async () => {
+    try {
+      const tokenAccount = anchor.utils.token.associatedAddress({
+        mint: accounts.masterNftMint,
+        owner: accounts.artist,
+      });
+
+      const moderate = async (status: object, reasonCode: number) => {
+        const txSignature = await program.methods
+          .moderateMasterNft(status as any, reasonCode)
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            role: null,
+            masterNft: accounts.masterNft,
+            mint: accounts.masterNftMint,
+            tokenAccount,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+
+        await confirm(txSignature).then(log);
+      };
+
+      await moderate({ takenDown: {} }, 7);
+
+      let masterNftAccount = await program.account.masterNft.fetch(accounts.masterNft);
+      expect(masterNftAccount.status).to.deep.equal({ takenDown: {} });
+      expect(masterNftAccount.moderationReason).to.equal(7);
+
+      // The artist can't close the track to get out from under the takedown
+      try {
+        await program.methods
+          .closeMasterNft()
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            masterNft: accounts.masterNft,
+            mint: accounts.masterNftMint,
+            isrcRegistration: null,
+            audioFingerprint: null,
+          })
+          .signers([artist])
+          .rpc();
+        expect.fail("A taken-down track should not close");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("TrackUnderModeration");
+      }
+
+      await moderate({ active: {} }, 0);
+
+      masterNftAccount = await program.account.masterNft.fetch(accounts.masterNft);
+      expect(masterNftAccount.status).to.deep.equal({ active: {} });
+    } catch (e) {
+      console.error("Error moderating master NFT:", e);
+      throw e;
+    }
+  });
+
+  it("Moderator can reassign or revoke a recording, and the last track out closes its entry", async () => {
+    try {
+      const recording = Array.from(createHash("sha256").update("disputed-recording-fingerprint").digest());
+      const squatted = Array.from(createHash("sha256").update("squatted-recording-fingerprint").digest());
+
+      const mintTrack = async (title: string, trackFingerprint: number[], versionKind: object | null) => {
+        const mintKeypair = Keypair.generate();
+        const [masterNftPDA] = PublicKey.findProgramAddressSync(
+          [Buffer.from("master_nft"), mintKeypair.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .mintMasterNft(
+            title,
+            masterNftDescription,
+            audioUri,
+            artworkUri,
+            audioHash,
+            artworkHash,
+            audioSpec as any,
+            metadata,
+            null,
+            trackFingerprint,
+            versionKind as any
+          )
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            masterNft: masterNftPDA,
+            delegate: null,
+            label: null,
+            artistWallet: accounts.artist,
+            mint: mintKeypair.publicKey,
+            feeVoucher: null,
+            referrer: null,
+            isrcRegistration: null,
+            audioFingerprint: findAudioFingerprintPDA(trackFingerprint),
+          })
+          .signers([artist, mintKeypair])
+          .rpc()
+          .then(confirm);
+        return { masterNft: masterNftPDA, mint: mintKeypair.publicKey };
+      };
+
+      const closeTrack = async (track: { masterNft: PublicKey; mint: PublicKey }, trackFingerprint: number[]) => {
+        await program.methods
+          .closeMasterNft()
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            masterNft: track.masterNft,
+            mint: track.mint,
+            isrcRegistration: null,
+            audioFingerprint: findAudioFingerprintPDA(trackFingerprint),
+          })
+          .signers([artist])
+          .rpc()
+          .then(confirm)
+          .then(log);
+      };
+
+      const original = await mintTrack("Disputed", recording, null);
+      const version = await mintTrack("Disputed (Remaster)", recording, { remaster: {} });
+      const claimant = await mintTrack("Squatted", squatted, null);
+
+      // Revoking frees the claimant's slot and opens the recording to anyone
+      await program.methods
+        .revokeFingerprint(4)
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          audioFingerprint: findAudioFingerprintPDA(squatted),
+          masterNft: claimant.masterNft,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const squattedEntry = await program.account.audioFingerprint.fetch(findAudioFingerprintPDA(squatted));
+      expect(squattedEntry.original.toString()).to.equal(PublicKey.default.toString());
+      expect(squattedEntry.artistProfile.toString()).to.equal(PublicKey.default.toString());
+      expect(squattedEntry.reasonCode).to.equal(4);
+      expect((await program.account.masterNft.fetch(claimant.masterNft)).fingerprint).to.deep.equal(
+        new Array(32).fill(0)
+      );
+
+      // Reassigning moves the original to a track without a recording of its own
+      await program.methods
+        .reassignFingerprint(5)
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          role: null,
+          audioFingerprint: findAudioFingerprintPDA(recording),
+          currentMasterNft: original.masterNft,
+          newMasterNft: claimant.masterNft,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      let entry = await program.account.audioFingerprint.fetch(findAudioFingerprintPDA(recording));
+      expect(entry.original.toString()).to.equal(claimant.masterNft.toString());
+      expect(entry.reassignedBy.toString()).to.equal(accounts.authority.toString());
+      expect(entry.versionCount).to.equal(1);
+      expect((await program.account.masterNft.fetch(claimant.masterNft)).fingerprint).to.deep.equal(recording);
+
+      // Closing the original first leaves the entry to the remaining version
+      await closeTrack(claimant, recording);
+      entry = await program.account.audioFingerprint.fetch(findAudioFingerprintPDA(recording));
+      expect(entry.original.toString()).to.equal(PublicKey.default.toString());
+      expect(entry.versionCount).to.equal(1);
+
+      // and the version closes it on its way out
+      await closeTrack(version, recording);
+      expect(await program.account.audioFingerprint.fetchNullable(findAudioFingerprintPDA(recording))).to.be.null;
+    } catch (e) {
+      console.error("Error moderating recording fingerprints:", e);
+      throw e;
+    }
+  });
+
+  it("Tracks revenue into the epoch of the period it was earned in", async () => {
+    try {
+      const now = new Date();
+      const previousPeriod = periodFor(new Date(Date.UTC(now.getUTCFullYear(), now.getUTCMonth() - 1, 1)));
+      const [revenueTrackerPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("revenue_tracker"), accounts.masterNft.toBuffer()],
+        program.programId
+      );
+      const revenueEpochPDA = findRevenueEpochPDA(accounts.masterNft, previousPeriod);
+
+      const trackRevenue = (period: number, revenueEpoch: PublicKey) =>
+        program.methods
+          .trackRevenue(new BN(1_000_000), "sales", "Album sales", period)
+          .accounts({
+            authority: accounts.artist,
+            masterNft: accounts.masterNft,
+            artistProfile: accounts.artistProfile,
+            label: null,
+            royaltySplit: accounts.royaltySplit,
+            revenueTracker: revenueTrackerPDA,
+            revenueEpoch,
+            treasury: accounts.treasury,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([artist])
+          .rpc();
+
+      // Late revenue can still be booked into an earlier, unclosed period
+      await trackRevenue(previousPeriod, revenueEpochPDA).then(confirm).then(log);
+
+      const revenueEpoch = await program.account.revenueEpoch.fetch(revenueEpochPDA);
+      expect(revenueEpoch.period).to.equal(previousPeriod);
+      expect(revenueEpoch.totalRevenue.toString()).to.equal("1000000");
+      expect(revenueEpoch.salesRevenue.toString()).to.equal("1000000");
+      expect(revenueEpoch.isClosed).to.be.false;
+
+      // Periods that have not started yet are rejected
+      const nextPeriod = periodFor(new Date(Date.UTC(now.getUTCFullYear(), now.getUTCMonth() + 1, 1)));
+      try {
+        await trackRevenue(nextPeriod, findRevenueEpochPDA(accounts.masterNft, nextPeriod));
+        expect.fail("Revenue for a future period should be rejected");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("InvalidEpochPeriod");
+      }
+
+      accounts.revenueTracker = revenueTrackerPDA;
+    } catch (e) {
+      console.error("Error tracking revenue:", e);
+      throw e;
+    }
+  });
+
+  it("Streaming batches open the epoch of each record's period on first use", async () => {
+    try {
+      const currentPeriod = periodFor(new Date());
+      const revenueEpochPDA = findRevenueEpochPDA(accounts.masterNft, currentPeriod);
+      const [feeOverridePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_override"), artistProfilePDA.toBuffer()],
+        program.programId
+      );
+      expect(await connection.getAccountInfo(revenueEpochPDA)).to.be.null;
+
+      const txSignature = await program.methods
+        .registerStreamingBatch([
+          { masterNft: accounts.masterNft, amount: new BN(500_000), bump: 0, period: currentPeriod },
+        ])
+        .accounts({
+          authority: accounts.streamingProvider,
+          streamingProvider: accounts.streamingProvider,
+          treasury: accounts.treasury,
+          systemProgram: accounts.systemProgram,
+        })
+        .remainingAccounts([
+          { pubkey: accounts.revenueTracker, isSigner: false, isWritable: true },
+          { pubkey: accounts.royaltySplit, isSigner: false, isWritable: true },
+          { pubkey: revenueEpochPDA, isSigner: false, isWritable: true },
+          { pubkey: accounts.masterNft, isSigner: false, isWritable: false },
+          { pubkey: accounts.artistProfile, isSigner: false, isWritable: false },
+          { pubkey: feeOverridePDA, isSigner: false, isWritable: false },
+        ])
+        .signers([streamingProvider])
+        .rpc();
+
+      await confirm(txSignature).then(log);
+
+      const revenueEpoch = await program.account.revenueEpoch.fetch(revenueEpochPDA);
+      expect(revenueEpoch.period).to.equal(currentPeriod);
+      expect(revenueEpoch.streamingRevenue.toString()).to.equal("500000");
+
+      const revenueTracker = await program.account.revenueTracker.fetch(accounts.revenueTracker);
+      expect(revenueTracker.totalRevenue.toString()).to.equal("1500000");
+    } catch (e) {
+      console.error("Error registering streaming batch:", e);
+      throw e;
+    }
+  });
+
+  it("Settled epochs are closed out and royalty NFTs stay bound to their share", async () => {
+    try {
+      const now = new Date();
+      const previousPeriod = periodFor(new Date(Date.UTC(now.getUTCFullYear(), now.getUTCMonth() - 1, 1)));
+      const revenueEpochPDA = findRevenueEpochPDA(accounts.masterNft, previousPeriod);
+      const royaltyMint = Keypair.generate();
+      const [royaltyNftPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("royalty_nft"), royaltyMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [royaltyStatementPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("royalty_statement"), revenueEpochPDA.toBuffer(), royaltyNftPDA.toBuffer()],
+        program.programId
+      );
+      const [feeOverridePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_override"), artistProfilePDA.toBuffer()],
+        program.programId
+      );
+      const royaltyTokenAccount = anchor.utils.token.associatedAddress({
+        mint: royaltyMint.publicKey,
+        owner: accounts.collaborator1,
+      });
+
+      // The producer takes their 20% as a royalty NFT
+      await program.methods
+        .mintRoyaltyNft(2000)
+        .accounts({
+          authority: accounts.collaborator1,
+          masterNft: accounts.masterNft,
+          royaltySplit: accounts.royaltySplit,
+          mint: royaltyMint.publicKey,
+          tokenAccount: royaltyTokenAccount,
+          royaltyNft: royaltyNftPDA,
+          treasury: accounts.treasury,
+        })
+        .signers([collaborator1, royaltyMint])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      // Royalty NFTs are fixed-supply from the moment they are minted
+      const royaltyMintInfo = await readMint(royaltyMint.publicKey);
+      expect(royaltyMintInfo.mintAuthority).to.be.null;
+      expect(royaltyMintInfo.supply.toNumber()).to.equal(1);
+
+      let royaltySplit = await program.account.royaltySplit.fetch(accounts.royaltySplit);
+      expect(royaltySplit.royaltyNftCount).to.equal(1);
+      expect(royaltySplit.mintedBasisPoints).to.equal(2000);
+      expect(royaltySplit.revenueEpochCount).to.equal(2);
+
+      await program.methods
+        .closeEpoch()
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          masterNft: accounts.masterNft,
+          revenueEpoch: revenueEpochPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const closeRevenueEpoch = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+        program.methods
+          .closeRevenueEpoch()
+          .accounts({
+            authority: accounts.artist,
+            artistProfile: accounts.artistProfile,
+            delegate: null,
+            label: null,
+            masterNft: accounts.masterNft,
+            royaltySplit: accounts.royaltySplit,
+            revenueEpoch: revenueEpochPDA,
+            payer: accounts.artist,
+          })
+          .remainingAccounts(remainingAccounts)
+          .signers([artist])
+          .rpc();
+
+      // The producer's share is still unpaid
+      try {
+        await closeRevenueEpoch([]);
+        expect.fail("An epoch with unpaid shares should not close");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("EpochNotSettled");
+      }
+
+      await program.methods
+        .claimRevenue()
+        .accounts({
+          authority: accounts.collaborator1,
+          royaltyNft: royaltyNftPDA,
+          masterNft: accounts.masterNft,
+          royaltySplit: accounts.royaltySplit,
+          artistProfile: accounts.artistProfile,
+          feeOverride: feeOverridePDA,
+          revenueEpoch: revenueEpochPDA,
+          royaltyStatement: royaltyStatementPDA,
+          treasury: accounts.treasury,
+          payer: accounts.artist,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([collaborator1, artist])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const royaltyStatement = await program.account.royaltyStatement.fetch(royaltyStatementPDA);
+      expect(royaltyStatement.grossAmount.toString()).to.equal("200000");
+      expect(royaltyStatement.claimant.toString()).to.equal(accounts.collaborator1.toString());
+
+      const revenueEpoch = await program.account.revenueEpoch.fetch(revenueEpochPDA);
+      expect(revenueEpoch.claimedBasisPoints).to.equal(2000);
+      expect(revenueEpoch.statementCount).to.equal(1);
+
+      // Statements have to be closed along with their epoch
+      try {
+        await closeRevenueEpoch([]);
+        expect.fail("An epoch cannot be closed without its statements");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("RoyaltyStatementsMissing");
+      }
+
+      await closeRevenueEpoch([
+        { pubkey: royaltyStatementPDA, isSigner: false, isWritable: true },
+        { pubkey: accounts.collaborator1, isSigner: false, isWritable: true },
+      ]).then(confirm).then(log);
+
+      expect(await connection.getAccountInfo(revenueEpochPDA)).to.be.null;
+      expect(await connection.getAccountInfo(royaltyStatementPDA)).to.be.null;
+      expect((await program.account.royaltySplit.fetch(accounts.royaltySplit)).closedThroughPeriod).to.equal(
+        previousPeriod
+      );
+
+      // The settled month can't be re-created by booking more revenue into it
+      try {
+        await program.methods
+          .trackRevenue(new BN(1_000_000), "sales", "Late album sales", previousPeriod)
+          .accounts({
+            authority: accounts.artist,
+            masterNft: accounts.masterNft,
+            artistProfile: accounts.artistProfile,
+            label: null,
+            royaltySplit: accounts.royaltySplit,
+            revenueTracker: accounts.revenueTracker,
+            revenueEpoch: revenueEpochPDA,
+            treasury: accounts.treasury,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([artist])
+          .rpc();
+        expect.fail("Revenue for a closed-out period should be rejected");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("EpochClosed");
+      }
+      expect(await connection.getAccountInfo(revenueEpochPDA)).to.be.null;
+
+      // Burning while the current month is still open would let the producer re-mint and
+      // claim the same epoch twice
+      try {
+        await program.methods
+          .closeRoyaltyNft()
+          .accounts({
+            authority: accounts.collaborator1,
+            masterNft: accounts.masterNft,
+            royaltySplit: accounts.royaltySplit,
+            mint: royaltyMint.publicKey,
+            tokenAccount: royaltyTokenAccount,
+            royaltyNft: royaltyNftPDA,
+          })
+          .signers([collaborator1])
+          .rpc();
+        expect.fail("A royalty NFT should not burn while epochs are open");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("UnclaimedRevenue");
+      }
+
+      // The producer's whole allocation is already minted
+      const extraMint = Keypair.generate();
+      try {
+        await program.methods
+          .mintRoyaltyNft(500)
+          .accounts({
+            authority: accounts.collaborator1,
+            masterNft: accounts.masterNft,
+            royaltySplit: accounts.royaltySplit,
+            mint: extraMint.publicKey,
+            tokenAccount: anchor.utils.token.associatedAddress({
+              mint: extraMint.publicKey,
+              owner: accounts.collaborator1,
+            }),
+            royaltyNft: PublicKey.findProgramAddressSync(
+              [Buffer.from("royalty_nft"), extraMint.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            treasury: accounts.treasury,
+          })
+          .signers([collaborator1, extraMint])
+          .rpc();
+        expect.fail("Minting beyond the collaborator's share should be rejected");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("InvalidRoyaltyShares");
+      }
+
+      royaltySplit = await program.account.royaltySplit.fetch(accounts.royaltySplit);
+      expect(royaltySplit.royaltyNftCount).to.equal(1);
+      expect(royaltySplit.mintedBasisPoints).to.equal(2000);
+      expect(royaltySplit.revenueEpochCount).to.equal(1);
+      expect(royaltySplit.mintedShares[0].basisPoints).to.equal(2000);
+      expect(await connection.getAccountInfo(royaltyNftPDA)).to.not.be.null;
+    } catch (e) {
+      console.error("Error closing out revenue accounts:", e);
+      throw e;
+    }
+  });
+
+  it("Remix revenue cascades the upstream cut into the parent's epochs", async () => {
+    try {
+      const now = new Date();
+      const latePeriod = periodFor(new Date(Date.UTC(now.getUTCFullYear(), now.getUTCMonth() - 2, 1)));
+      const currentPeriod = periodFor(now);
+      const parentLateEpochPDA = findRevenueEpochPDA(accounts.masterNft, latePeriod);
+      const parentCurrentEpochPDA = findRevenueEpochPDA(accounts.masterNft, currentPeriod);
+      const remixEpochPDA = findRevenueEpochPDA(accounts.remix, latePeriod);
+      const [remixTrackerPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("revenue_tracker"), accounts.remix.toBuffer()],
+        program.programId
+      );
+
+      // The parent closed out last month, so the remix's late revenue from the month before
+      // can't reopen that period on the parent
+      const parentSplitBefore = await program.account.royaltySplit.fetch(accounts.royaltySplit);
+      expect(parentSplitBefore.closedThroughPeriod).to.be.above(latePeriod);
+
+      const parentEpochBefore = await program.account.revenueEpoch.fetch(parentCurrentEpochPDA);
+
+      await program.methods
+        .trackRevenue(new BN(1_000_000), "sales", "Remix sales", latePeriod)
+        .accounts({
+          authority: accounts.artist,
+          masterNft: accounts.remix,
+          artistProfile: accounts.artistProfile,
+          label: null,
+          royaltySplit: accounts.remixSplit,
+          revenueTracker: remixTrackerPDA,
+          revenueEpoch: remixEpochPDA,
+          treasury: accounts.treasury,
+          systemProgram: accounts.systemProgram,
+        })
+        .remainingAccounts([
+          { pubkey: accounts.royaltySplit, isSigner: false, isWritable: true },
+          { pubkey: parentLateEpochPDA, isSigner: false, isWritable: true },
+          { pubkey: parentCurrentEpochPDA, isSigner: false, isWritable: true },
+          { pubkey: accounts.masterNft, isSigner: false, isWritable: false },
+          { pubkey: accounts.artistProfile, isSigner: false, isWritable: false },
+        ])
+        .signers([artist])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      // The remix keeps 75% and the parent's 25% lands in its current period
+      const remixEpoch = await program.account.revenueEpoch.fetch(remixEpochPDA);
+      expect(remixEpoch.totalRevenue.toString()).to.equal("750000");
+
+      expect(await connection.getAccountInfo(parentLateEpochPDA)).to.be.null;
+
+      const parentEpochAfter = await program.account.revenueEpoch.fetch(parentCurrentEpochPDA);
+      expect(parentEpochAfter.salesRevenue.sub(parentEpochBefore.salesRevenue).toString()).to.equal("250000");
+
+      const remixSplit = await program.account.royaltySplit.fetch(accounts.remixSplit);
+      expect(remixSplit.upstream[0].amountPaid.toString()).to.equal("250000");
+      expect(remixSplit.totalRevenueCollected.toString()).to.equal("750000");
+
+      const parentSplit = await program.account.royaltySplit.fetch(accounts.royaltySplit);
+      expect(parentSplit.revenueEpochCount).to.equal(parentSplitBefore.revenueEpochCount);
+    } catch (e) {
+      console.error("Error cascading remix revenue:", e);
+      throw e;
+    }
+  });
+
+  it("Layout migrations leave accounts in the current layout untouched", async () => {
+    const migrations = [
+      program.methods.migrateTreasury().accounts({
+        authority: accounts.authority,
+        treasury: accounts.treasury,
+        systemProgram: accounts.systemProgram,
+      }),
+      program.methods.migrateArtistProfile().accounts({
+        authority: accounts.authority,
+        treasury: accounts.treasury,
+        artistProfile: accounts.artistProfile,
+        systemProgram: accounts.systemProgram,
+      }),
+      program.methods.migrateMasterNft().accounts({
+        authority: accounts.authority,
+        treasury: accounts.treasury,
+        masterNft: accounts.masterNft,
+        systemProgram: accounts.systemProgram,
+      }),
+    ];
+
+    for (const migration of migrations) {
+      try {
+        await migration.signers([authority]).rpc();
+        expect.fail("Migrating a current-layout account should be rejected");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("AccountAlreadyMigrated");
+      }
+    }
+  });
+
+  // Enabling the multisig locks out the single-key admin paths, so this runs last
+  it("Admin multisig can approve and execute a proposal", async () => {
+    try {
+      const secondAdmin = Keypair.generate();
+
+      // Configure a 2-of-2 admin set
+      const configureSignature = await program.methods
+        .configureAdminMultisig([authority.publicKey, secondAdmin.publicKey], 2)
+        .accounts({
+          authority: accounts.authority,
+          treasury: accounts.treasury,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(configureSignature).then(log);
+
+      const treasuryBefore = await program.account.treasury.fetch(treasuryPDA);
+      const [proposalPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("admin_proposal"),
+          treasuryPDA.toBuffer(),
+          treasuryBefore.proposalCount.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      // Propose a new streaming provider
+      const newStreamingProvider = Keypair.generate().publicKey;
+      const proposeSignature = await program.methods
+        .proposeAdminAction({
+          updateStreamingProvider: { newStreamingProvider },
+        })
+        .accounts({
+          proposer: accounts.authority,
+          treasury: accounts.treasury,
+          proposal: proposalPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(proposeSignature).then(log);
+
+      // Direct admin changes are rejected while the multisig is active
+      try {
+        await program.methods
+          .updateStreamingProvider(newStreamingProvider)
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            role: null,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Direct admin update should be rejected");
+      } catch (e) {
+        expect(e.toString()).to.include("MultisigRequired");
+      }
+
+      // Roles can no longer be handed out by the single authority either
+      const moderator = Keypair.generate();
+      const [moderatorRolePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("admin_role"), moderator.publicKey.toBuffer(), Buffer.from([4])],
+        program.programId
+      );
+      try {
+        await program.methods
+          .grantRole({ moderator: {} })
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            holder: moderator.publicKey,
+            adminRole: moderatorRolePDA,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Direct role grant should be rejected");
+      } catch (e) {
+        expect(e.toString()).to.include("MultisigRequired");
+      }
+
+      // Verifiers can't approve verification requests on their own either
+      const [verificationRequestPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("verification_request"), artistProfilePDA.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .submitVerificationRequest("https://soundmint.com/press/artist-kit", { official: {} })
+        .accounts({
+          authority: accounts.artist,
+          artistProfile: accounts.artistProfile,
+          verificationRequest: verificationRequestPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([artist])
+        .rpc()
+        .then(confirm);
+      try {
+        await program.methods
+          .reviewVerificationRequest(true, null, 0)
+          .accounts({
+            authority: accounts.authority,
+            artistProfile: accounts.artistProfile,
+            verificationRequest: verificationRequestPDA,
+            treasury: accounts.treasury,
+            role: null,
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Direct verification review should be rejected");
+      } catch (e) {
+        expect(e.toString()).to.include("MultisigRequired");
+      }
+
+      // Fee waivers go through a proposal too
+      try {
+        await program.methods
+          .issueFeeVoucher(3, new BN(Math.floor(Date.now() / 1000) + 24 * 60 * 60))
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            role: null,
+            artistProfile: accounts.artistProfile,
+            feeVoucher: PublicKey.findProgramAddressSync(
+              [Buffer.from("fee_voucher"), artistProfilePDA.toBuffer()],
+              program.programId
+            )[0],
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Direct fee voucher issuance should be rejected");
+      } catch (e) {
+        expect(e.toString()).to.include("MultisigRequired");
+      }
+
+      // As are protected handle reservations
+      try {
+        await program.methods
+          .reserveArtistHandle("Cosmic Supernova")
+          .accounts({
+            authority: accounts.authority,
+            treasury: accounts.treasury,
+            role: null,
+            artistProfile: accounts.artistProfile,
+            artistHandle: findArtistHandlePDA("Cosmic Supernova"),
+            systemProgram: accounts.systemProgram,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Direct handle reservation should be rejected");
+      } catch (e) {
+        expect(e.toString()).to.include("MultisigRequired");
+      }
+
+      const approveSignature = await program.methods
+        .approveAdminProposal()
+        .accounts({
+          admin: secondAdmin.publicKey,
+          treasury: accounts.treasury,
+          proposal: proposalPDA,
+        })
+        .signers([secondAdmin])
+        .rpc();
+
+      await confirm(approveSignature).then(log);
+
+      const executeSignature = await program.methods
+        .executeAdminProposal()
+        .accounts({
+          admin: accounts.authority,
+          treasury: accounts.treasury,
+          proposal: proposalPDA,
+          treasuryWallet: null,
+          artistProfile: null,
+          adminRole: null,
+          feeOverride: null,
+          feeVoucher: null,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+      await confirm(executeSignature).then(log);
+
+      const proposalAccount = await program.account.adminProposal.fetch(proposalPDA);
+      const treasuryAccount = await program.account.treasury.fetch(treasuryPDA);
+
+      expect(proposalAccount.executed).to.be.true;
+      expect(proposalAccount.approvals).to.have.lengthOf(2);
+      expect(treasuryAccount.adminThreshold).to.equal(2);
+      expect(treasuryAccount.streamingProvider.toString()).to.equal(newStreamingProvider.toString());
+      expect(proposalAccount.expiresAt.toNumber()).to.equal(
+        proposalAccount.createdAt.toNumber() + 7 * 24 * 60 * 60
+      );
+
+      // Executed proposals can be closed, returning their rent to the proposer
+      await program.methods
+        .closeAdminProposal()
+        .accounts({
+          authority: secondAdmin.publicKey,
+          treasury: accounts.treasury,
+          proposal: proposalPDA,
+          proposer: accounts.authority,
+        })
+        .signers([secondAdmin])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      expect(await provider.connection.getAccountInfo(proposalPDA)).to.be.null;
+
+      // A role account someone has already sent lamports to can still be created
+      await provider
+        .sendAndConfirm(
+          new Transaction().add(
+            SystemProgram.transfer({
+              fromPubkey: provider.publicKey,
+              toPubkey: moderatorRolePDA,
+              lamports: 1_000,
+            })
+          ) as any
+        )
+        .then(confirm)
+        .then(log);
+
+      const [grantProposalPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("admin_proposal"),
+          treasuryPDA.toBuffer(),
+          treasuryAccount.proposalCount.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeAdminAction({
+          grantRole: { holder: moderator.publicKey, role: { moderator: {} } },
+        })
+        .accounts({
+          proposer: accounts.authority,
+          treasury: accounts.treasury,
+          proposal: grantProposalPDA,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      await program.methods
+        .approveAdminProposal()
+        .accounts({
+          admin: secondAdmin.publicKey,
+          treasury: accounts.treasury,
+          proposal: grantProposalPDA,
+        })
+        .signers([secondAdmin])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      await program.methods
+        .executeAdminProposal()
+        .accounts({
+          admin: accounts.authority,
+          treasury: accounts.treasury,
+          proposal: grantProposalPDA,
+          treasuryWallet: null,
+          artistProfile: null,
+          adminRole: moderatorRolePDA,
+          feeOverride: null,
+          feeVoucher: null,
+          systemProgram: accounts.systemProgram,
+        })
+        .signers([authority])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const moderatorRole = await program.account.adminRole.fetch(moderatorRolePDA);
+      expect(moderatorRole.holder.toString()).to.equal(moderator.publicKey.toString());
+      expect(moderatorRole.role).to.deep.equal({ moderator: {} });
+    } catch (e) {
+      console.error("Error executing admin multisig proposal:", e);
       throw e;
     }
   });